@@ -8,9 +8,17 @@ trait ExtContract {
         &mut self,
         receiver_id: AccountId,
         token_id: TokenId,
-        approval_id: U64,
-        msg: Option<String>,
+        approval_id: Option<u64>,
+        memo: Option<String>,
         balance: U128,
+        max_len_payout: Option<u32>,
+    );
+    fn lazy_mint_payout(
+        &mut self,
+        receiver_id: AccountId,
+        series_mint_args: SeriesMintArgs,
+        balance: U128,
+        max_len_payout: Option<u32>,
     );
     fn ft_transfer(
         &mut self,
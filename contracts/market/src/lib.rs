@@ -11,13 +11,19 @@ use std::collections::HashMap;
 
 use crate::external::*;
 use crate::internal::*;
+use crate::migrate::*;
+use crate::pause::*;
+use crate::roles::*;
 use crate::sale::*;
 use near_sdk::env::STORAGE_PRICE_PER_BYTE;
 
 mod external;
 mod ft_callbacks;
 mod internal;
+mod migrate;
 mod nft_callbacks;
+mod pause;
+mod roles;
 mod sale;
 mod sale_views;
 
@@ -40,8 +46,12 @@ pub type TokenId = String;
 pub type TokenType = Option<String>;
 pub type FungibleTokenId = AccountId;
 pub type ContractAndTokenId = String;
-// TODO: Capital U128
-pub type Payout = HashMap<AccountId, U128>;
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Payout {
+    pub payout: HashMap<AccountId, U128>,
+}
 
 #[near_bindgen]
 #[derive(BorshDeserialize, BorshSerialize, PanicOnDefault)]
@@ -53,6 +63,10 @@ pub struct Contract {
     pub by_nft_token_type: LookupMap<AccountId, UnorderedSet<ContractAndTokenId>>,
     pub ft_token_ids: UnorderedSet<AccountId>,
     pub storage_deposits: LookupMap<AccountId, Balance>,
+    pub paused_features: UnorderedSet<Feature>,
+    pub pending_owner_id: Option<AccountId>,
+    pub accounts_per_role: LookupMap<Role, UnorderedSet<AccountId>>,
+    pub state_version: u16,
 }
 
 /// Helper structure to for keys of the persistent collections.
@@ -67,6 +81,9 @@ pub enum StorageKey {
     ByNFTTokenTypeInner { token_type_hash: CryptoHash },
     FTTokenIds,
     StorageDeposits,
+    PausedFeatures,
+    AccountsPerRole,
+    AccountsPerRoleInner { role: Role },
 }
 
 #[near_bindgen]
@@ -81,6 +98,10 @@ impl Contract {
             by_nft_token_type: LookupMap::new(StorageKey::ByNFTTokenType),
             ft_token_ids: UnorderedSet::new(StorageKey::FTTokenIds),
             storage_deposits: LookupMap::new(StorageKey::StorageDeposits),
+            paused_features: UnorderedSet::new(StorageKey::PausedFeatures),
+            pending_owner_id: None,
+            accounts_per_role: LookupMap::new(StorageKey::AccountsPerRole),
+            state_version: STATE_VERSION,
         };
         // support NEAR by default
         this.ft_token_ids.insert(&"near".to_string());
@@ -94,9 +115,9 @@ impl Contract {
         this
     }
 
-    /// only owner
+    /// only owner or curator
     pub fn add_ft_token_ids(&mut self, ft_token_ids: Vec<ValidAccountId>) -> Vec<bool> {
-        self.assert_owner();
+        self.assert_role(Role::Curator);
         ft_token_ids
             .into_iter()
             .map(|ft_token_id| self.ft_token_ids.insert(ft_token_id.as_ref()))
@@ -0,0 +1,74 @@
+use crate::*;
+
+/// bump whenever the layout of `Contract` or of the stored `Sale` records changes and add the
+/// previous layouts below. Version 1 is the layout before state versioning, `Sale` is unchanged
+/// since so sales need no migration
+pub const STATE_VERSION: u16 = 2;
+
+/// layout before state versioning
+#[derive(BorshDeserialize)]
+pub struct ContractV1 {
+    pub owner_id: AccountId,
+    pub sales: UnorderedMap<ContractAndTokenId, Sale>,
+    pub by_owner_id: LookupMap<AccountId, UnorderedSet<ContractAndTokenId>>,
+    pub by_nft_contract_id: LookupMap<AccountId, UnorderedSet<ContractAndTokenId>>,
+    pub by_nft_token_type: LookupMap<AccountId, UnorderedSet<ContractAndTokenId>>,
+    pub ft_token_ids: UnorderedSet<AccountId>,
+    pub storage_deposits: LookupMap<AccountId, Balance>,
+}
+
+pub enum VersionedContract {
+    V1(ContractV1),
+    Current(Contract),
+}
+
+impl VersionedContract {
+    /// reads the raw contract state and detects which layout it was written with
+    pub fn read() -> Self {
+        let state = env::storage_read(b"STATE").expect("Contract is not initialized");
+        if let Ok(contract) = Contract::try_from_slice(&state) {
+            return VersionedContract::Current(contract);
+        }
+        if let Ok(contract) = ContractV1::try_from_slice(&state) {
+            return VersionedContract::V1(contract);
+        }
+        env::panic(b"Unknown contract state layout")
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// only owner - call after deploying new code to upgrade existing state
+    #[init(ignore_state)]
+    pub fn migrate() -> Self {
+        let contract = match VersionedContract::read() {
+            VersionedContract::V1(old) => Contract {
+                owner_id: old.owner_id,
+                sales: old.sales,
+                by_owner_id: old.by_owner_id,
+                by_nft_contract_id: old.by_nft_contract_id,
+                by_nft_token_type: old.by_nft_token_type,
+                ft_token_ids: old.ft_token_ids,
+                storage_deposits: old.storage_deposits,
+                paused_features: UnorderedSet::new(StorageKey::PausedFeatures),
+                pending_owner_id: None,
+                accounts_per_role: LookupMap::new(StorageKey::AccountsPerRole),
+                state_version: STATE_VERSION,
+            },
+            VersionedContract::Current(contract) => {
+                assert!(contract.state_version < STATE_VERSION, "Already migrated");
+                Contract {
+                    state_version: STATE_VERSION,
+                    ..contract
+                }
+            }
+        };
+        contract.assert_owner();
+        contract
+    }
+
+    /// views
+    pub fn get_state_version(&self) -> u16 {
+        self.state_version
+    }
+}
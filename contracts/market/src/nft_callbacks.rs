@@ -10,6 +10,8 @@ trait NonFungibleTokenApprovalsReceiver {
         approval_id: U64,
         msg: String,
     );
+
+    fn nft_on_revoke(&mut self, token_id: TokenId);
 }
 
 #[near_bindgen]
@@ -22,6 +24,7 @@ impl NonFungibleTokenApprovalsReceiver for Contract {
         approval_id: U64,
         msg: String,
     ) {
+        self.assert_not_paused(Feature::Listing);
         self.check_valid_callback(owner_id.clone());
 
         let nft_contract_id = env::predecessor_account_id();
@@ -101,6 +104,17 @@ impl NonFungibleTokenApprovalsReceiver for Contract {
                 .insert(&token_type, &by_nft_token_type);
         }
     }
+
+    /// owner revoked the market's approval, sale can no longer complete so remove it
+    fn nft_on_revoke(&mut self, token_id: TokenId) {
+        let nft_contract_id = env::predecessor_account_id();
+        let contract_and_token_id = format!("{}{}{}", nft_contract_id, DELIMETER, token_id);
+        if self.sales.get(&contract_and_token_id).is_none() {
+            return;
+        }
+        let sale = self.internal_remove_sale(nft_contract_id, token_id);
+        self.refund_bids(sale.bids.unwrap_or_default());
+    }
 }
 
 trait NonFungibleSeriesApprovalReceiver {
@@ -111,6 +125,7 @@ trait NonFungibleSeriesApprovalReceiver {
 impl NonFungibleSeriesApprovalReceiver for Contract {
     #[payable]
     fn series_on_approve(&mut self, series_name: String, owner_id: ValidAccountId, msg: String) {
+        self.assert_not_paused(Feature::Listing);
         self.check_valid_callback(owner_id.clone());
 
         let nft_contract_id = env::predecessor_account_id();
@@ -6,6 +6,8 @@ const GAS_FOR_FT_TRANSFER: Gas = 5_000_000_000_000;
 /// seems to be max Tgas can attach to resolve_purchase
 const GAS_FOR_ROYALTIES: Gas = 120_000_000_000_000;
 const GAS_FOR_NFT_TRANSFER: Gas = 20_000_000_000_000;
+/// gas to do 10 FT transfers (and definitely 10 NEAR transfers)
+const MAX_LEN_PAYOUT: u32 = 10;
 
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
 #[serde(crate = "near_sdk::serde")]
@@ -50,6 +52,7 @@ pub struct SeriesMintArgs {
     pub owner: Vec<String>,
     pub perpetual_royalties: Option<HashMap<AccountId, u32>>,
     pub receiver_id: Option<ValidAccountId>,
+    pub media: Option<String>,
 }
 
 #[near_bindgen]
@@ -146,6 +149,7 @@ impl Contract {
         price: U128,
         buyer_id: AccountId,
     ) -> Promise {
+        self.assert_not_paused(Feature::Purchasing);
         if sale.is_series.is_none() {
             self.internal_remove_sale(nft_contract_id.clone(), token_id.clone());
         }
@@ -155,18 +159,38 @@ impl Contract {
             nft_transfer_deposit = 1
         }
 
-        ext_contract::nft_transfer_payout(
-            buyer_id.clone(),
-            token_id,
-            sale.approval_id,
-            msg,
-            price,
-            &nft_contract_id,
-            // price paid remains with contract (excess deposit for storage cost of series lazy mint)
-            nft_transfer_deposit,
-            GAS_FOR_NFT_TRANSFER,
-        )
-        .then(ext_self::resolve_purchase(
+        // outstanding bids are refunded alongside the payout, see resolve_purchase
+        let max_len_payout =
+            MAX_LEN_PAYOUT.saturating_sub(sale.bids.as_ref().map_or(0, |bids| bids.len() as u32));
+
+        let payout_promise = if let Some(msg) = msg {
+            let series_mint_args: SeriesMintArgs =
+                near_sdk::serde_json::from_str(&msg).expect("Invalid SeriesMintArgs");
+            ext_contract::lazy_mint_payout(
+                buyer_id.clone(),
+                series_mint_args,
+                price,
+                Some(max_len_payout),
+                &nft_contract_id,
+                // price paid remains with contract (excess deposit for storage cost of series lazy mint)
+                nft_transfer_deposit,
+                GAS_FOR_NFT_TRANSFER,
+            )
+        } else {
+            ext_contract::nft_transfer_payout(
+                buyer_id.clone(),
+                token_id,
+                Some(sale.approval_id.0),
+                None,
+                price,
+                Some(max_len_payout),
+                &nft_contract_id,
+                nft_transfer_deposit,
+                GAS_FOR_NFT_TRANSFER,
+            )
+        };
+
+        payout_promise.then(ext_self::resolve_purchase(
             ft_token_id,
             buyer_id,
             sale,
@@ -189,28 +213,29 @@ impl Contract {
     ) -> U128 {
         let bids = sale.bids.unwrap_or_default();
         let price = sale.conditions[&ft_token_id];
+        let seller_id = &sale.owner_id;
 
         // checking for payout information
         let payout_option = promise_result_as_success().and_then(|value| {
             // None means a bad payout from bad NFT contract
             near_sdk::serde_json::from_slice::<Payout>(&value)
                 .ok()
-                .and_then(|payout| {
-                    // gas to do 10 FT transfers (and definitely 10 NEAR transfers)
-                    if payout.len() + bids.len() > 10 || payout.is_empty() {
+                .map(|payout| payout.payout)
+                .and_then(|mut payout| {
+                    if payout.len() + bids.len() > MAX_LEN_PAYOUT as usize || payout.is_empty() {
                         log!("Cannot have more than 10 royalties and sale.bids refunds");
                         None
                     } else {
-                        // TODO off by 1 e.g. payouts are fractions of 3333 + 3333 + 3333
+                        // payouts may not exceed the price, rounding dust left by contracts that
+                        // round each share down goes to the seller
                         let mut remainder = price.0;
                         for &value in payout.values() {
                             remainder = remainder.checked_sub(value.0)?;
                         }
-                        if remainder == 0 || remainder == 1 {
-                            Some(payout)
-                        } else {
-                            None
+                        if remainder > 0 {
+                            payout.entry(seller_id.clone()).or_insert(U128(0)).0 += remainder;
                         }
+                        Some(payout)
                     }
                 })
         });
@@ -261,6 +286,7 @@ impl Contract {
         ft_token_id: AccountId,
         buyer_id: AccountId,
     ) {
+        self.assert_not_paused(Feature::Bidding);
         assert!(
             price == 0 || amount < price,
             "Paid more {} than price {}",
@@ -324,7 +350,7 @@ impl Contract {
 
     /// internal
 
-    fn refund_bids(&mut self, bids: HashMap<FungibleTokenId, Bid>) {
+    pub(crate) fn refund_bids(&mut self, bids: HashMap<FungibleTokenId, Bid>) {
         for (bid_ft, bid) in bids {
             if bid_ft == "near" {
                 Promise::new(bid.owner_id.clone()).transfer(u128::from(bid.price));
@@ -69,6 +69,7 @@ impl Contract {
         &self,
         series_name: String,
     ) -> U64 {
+        let series_name = normalize_series_name(&series_name);
         let tokens_per_series = self.tokens_per_series.get(&series_name);
         if let Some(tokens_per_series) = tokens_per_series {
             U64(tokens_per_series.len())
@@ -83,6 +84,7 @@ impl Contract {
         from_index: U64,
         limit: u64,
     ) -> Vec<JsonToken> {
+        let series_name = normalize_series_name(&series_name);
         let mut tmp = vec![];
         let tokens_per_series = self.tokens_per_series.get(&series_name);
         let tokens_per_series = if let Some(tokens_per_series) = tokens_per_series {
@@ -105,7 +107,7 @@ impl Contract {
     ) -> Vec<U64> {
         let mut tmp = vec![];
         for series_name in series_names {
-            let tokens_per_series = self.tokens_per_series.get(&series_name);
+            let tokens_per_series = self.tokens_per_series.get(&normalize_series_name(&series_name));
             if let Some(tokens_per_series) = tokens_per_series {
                 tmp.push(U64(tokens_per_series.len()))
             } else {
@@ -0,0 +1,123 @@
+use crate::*;
+use std::fmt;
+
+pub const NFT_STANDARD_NAME: &str = "nep171";
+pub const NFT_EVENT_VERSION: &str = "1.0.0";
+// CUSTOM
+pub const GNR8_STANDARD_NAME: &str = "gnr8";
+pub const GNR8_EVENT_VERSION: &str = "1.0.0";
+
+/// NEP-297 event log, written as `EVENT_JSON:{...}`
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct EventLog {
+    pub standard: String,
+    pub version: String,
+    #[serde(flatten)]
+    pub event: EventLogVariant,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(tag = "event", content = "data")]
+#[serde(rename_all = "snake_case")]
+pub enum EventLogVariant {
+    NftMint(Vec<NftMintLog>),
+    NftTransfer(Vec<NftTransferLog>),
+    NftBurn(Vec<NftBurnLog>),
+    // CUSTOM
+    SeriesCreate(Vec<SeriesCreateLog>),
+    SeriesUpdate(Vec<SeriesUpdateLog>),
+    SeriesTransfer(Vec<SeriesTransferLog>),
+    UpdateTokenOwnerArgs(Vec<UpdateTokenOwnerArgsLog>),
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct NftMintLog {
+    pub owner_id: AccountId,
+    pub token_ids: Vec<TokenId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct NftTransferLog {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authorized_id: Option<AccountId>,
+    pub old_owner_id: AccountId,
+    pub new_owner_id: AccountId,
+    pub token_ids: Vec<TokenId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct NftBurnLog {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authorized_id: Option<AccountId>,
+    pub owner_id: AccountId,
+    pub token_ids: Vec<TokenId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SeriesCreateLog {
+    pub owner_id: AccountId,
+    pub series_name: SeriesName,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SeriesUpdateLog {
+    pub owner_id: AccountId,
+    pub series_name: SeriesName,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SeriesTransferLog {
+    pub old_owner_id: AccountId,
+    pub new_owner_id: AccountId,
+    pub series_name: SeriesName,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct UpdateTokenOwnerArgsLog {
+    pub owner_id: AccountId,
+    pub token_id: TokenId,
+    pub owner_args: Vec<String>,
+}
+
+impl EventLogVariant {
+    pub(crate) fn emit(self) {
+        let (standard, version) = match self {
+            EventLogVariant::NftMint(_)
+            | EventLogVariant::NftTransfer(_)
+            | EventLogVariant::NftBurn(_) => (NFT_STANDARD_NAME, NFT_EVENT_VERSION),
+            _ => (GNR8_STANDARD_NAME, GNR8_EVENT_VERSION),
+        };
+        log!(
+            "{}",
+            EventLog {
+                standard: standard.to_string(),
+                version: version.to_string(),
+                event: self,
+            }
+        );
+    }
+}
+
+impl fmt::Display for EventLog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_fmt(format_args!(
+            "EVENT_JSON:{}",
+            near_sdk::serde_json::to_string(self).map_err(|_| fmt::Error)?
+        ))
+    }
+}
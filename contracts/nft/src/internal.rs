@@ -1,5 +1,5 @@
 use crate::*;
-use near_sdk::{log, CryptoHash};
+use near_sdk::CryptoHash;
 
 pub(crate) fn royalty_to_payout(a: u32, b: Balance) -> U128 {
     U128(a as u128 * b / 10_000u128)
@@ -11,6 +11,24 @@ pub(crate) fn hash_account_id(account_id: &AccountId) -> CryptoHash {
     hash
 }
 
+pub(crate) fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// hex encoded sha256, same format as package src_hash from js-sha256
+pub(crate) fn hex_sha256(bytes: &[u8]) -> String {
+    hex_encode(&env::sha256(bytes))
+}
+
+/// borsh (series_name, args) so hashes are scoped per series and args can't run together
+pub(crate) fn hash_series_args(series_name: &str, args: &[String]) -> CryptoHash {
+    let mut bytes = series_name.try_to_vec().unwrap();
+    bytes.extend(args.try_to_vec().unwrap());
+    let mut hash = CryptoHash::default();
+    hash.copy_from_slice(&env::sha256(&bytes));
+    hash
+}
+
 pub(crate) fn assert_at_least_one_yocto() {
     assert!(
         env::attached_deposit() >= 1,
@@ -18,28 +36,35 @@ pub(crate) fn assert_at_least_one_yocto() {
     )
 }
 
-pub(crate) fn refund_deposit(initial_storage: u64, storage_used: u64, receiver_id: Option<AccountId>) {
-
-    let refund = if storage_used > initial_storage {
-        env::attached_deposit()
-            .checked_sub(env::storage_byte_cost() * Balance::from(storage_used - initial_storage))
-            .expect("Insufficient deposit to pay for storage")
-    } else {
-        env::attached_deposit() + env::storage_byte_cost() * Balance::from(initial_storage - storage_used)
-    };
-
-    if refund > 1 {
-        Promise::new(receiver_id.unwrap_or_else(env::predecessor_account_id)).transfer(refund);
-    }
-    
-}
-
-// TODO: need a way for end users to determine how much an approval will cost.
 pub(crate) fn bytes_for_approved_account_id(account_id: &AccountId) -> u64 {
     // The extra 4 bytes are coming from Borsh serialization to store the length of the string.
     account_id.len() as u64 + 4 + size_of::<u64>() as u64
 }
 
+/// schedules nft_on_revoke for each account, panics unless prepaid gas covers every notification
+/// so receivers such as markets can rely on being told
+pub(crate) fn notify_revoked<I>(token_id: &str, account_ids: I)
+where
+    I: IntoIterator<Item = AccountId>,
+{
+    let account_ids: Vec<AccountId> = account_ids.into_iter().collect();
+    let required_gas = GAS_FOR_NFT_ON_REVOKE * account_ids.len() as Gas + GAS_RESERVED_AFTER_REVOKE;
+    assert!(
+        env::prepaid_gas().saturating_sub(env::used_gas()) >= required_gas,
+        "Requires {} gas to notify {} revoked accounts",
+        required_gas,
+        account_ids.len()
+    );
+    for account_id in account_ids {
+        ext_non_fungible_approval_receiver::nft_on_revoke(
+            token_id.to_string(),
+            &account_id,
+            NO_DEPOSIT,
+            GAS_FOR_NFT_ON_REVOKE,
+        );
+    }
+}
+
 pub(crate) fn refund_approved_account_ids_iter<'a, I>(
     account_id: AccountId,
     approved_account_ids: I,
@@ -69,6 +94,54 @@ impl Contract {
         );
     }
 
+    /// Pays for storage from the attached deposit, drawing any shortfall from the
+    /// predecessor's prepaid storage balance, and refunds what is left of the deposit.
+    pub(crate) fn internal_refund_deposit(
+        &mut self,
+        initial_storage: u64,
+        storage_used: u64,
+        receiver_id: Option<AccountId>,
+    ) {
+        let refund = self.internal_settle_storage(env::attached_deposit(), initial_storage, storage_used);
+
+        if refund > 1 {
+            Promise::new(receiver_id.unwrap_or_else(env::predecessor_account_id)).transfer(refund);
+        }
+    }
+
+    /// Returns what is left of `deposit` after paying for storage growth, plus any freed storage.
+    pub(crate) fn internal_settle_storage(
+        &mut self,
+        deposit: Balance,
+        initial_storage: u64,
+        storage_used: u64,
+    ) -> Balance {
+        if storage_used > initial_storage {
+            self.internal_pay_storage(deposit, storage_used - initial_storage)
+        } else {
+            deposit + env::storage_byte_cost() * Balance::from(initial_storage - storage_used)
+        }
+    }
+
+    /// Returns what is left of `deposit` after paying for `bytes_used` of storage.
+    pub(crate) fn internal_pay_storage(&mut self, deposit: Balance, bytes_used: u64) -> Balance {
+        let storage_cost = env::storage_byte_cost() * Balance::from(bytes_used);
+        if deposit >= storage_cost {
+            return deposit - storage_cost;
+        }
+
+        let account_id = env::predecessor_account_id();
+        let shortfall = storage_cost - deposit;
+        let balance = self.storage_deposits.get(&account_id).unwrap_or(0);
+        assert!(
+            balance >= storage_balance_min() + shortfall,
+            "Insufficient deposit to pay for storage"
+        );
+        self.storage_deposits.insert(&account_id, &(balance - shortfall));
+
+        0
+    }
+
     pub(crate) fn internal_add_token_to_owner(
         &mut self,
         account_id: &AccountId,
@@ -102,6 +175,60 @@ impl Contract {
         }
     }
 
+    pub(crate) fn internal_remove_token(
+        &mut self,
+        owner_id: &AccountId,
+        token_id: &TokenId,
+    ) -> TokenData {
+        self.tokens_by_id.remove(token_id);
+        self.internal_remove_token_from_owner(owner_id, token_id);
+
+        let token_data = self
+            .token_data_by_id
+            .remove(token_id)
+            .unwrap_or_else(|| panic!("No token_data {}", token_id));
+        self.owner_args_history_by_id.remove(token_id);
+        let series_name = &token_data.series_args.series_name;
+        let series = self
+            .series_by_name
+            .get(series_name)
+            .unwrap_or_else(|| panic!("No series {}", series_name));
+
+        let mut tokens_per_series = self
+            .tokens_per_series
+            .get(series_name)
+            .expect("Token should be in series");
+        tokens_per_series.remove(token_id);
+        if tokens_per_series.is_empty() {
+            self.tokens_per_series.remove(series_name);
+        } else {
+            self.tokens_per_series.insert(series_name, &tokens_per_series);
+        }
+
+        for package in series.params.packages.iter() {
+            if let Some(mut tokens_per_package) = self.tokens_per_package.get(package) {
+                tokens_per_package.remove(token_id);
+                if tokens_per_package.is_empty() {
+                    self.tokens_per_package.remove(package);
+                } else {
+                    self.tokens_per_package.insert(package, &tokens_per_package);
+                }
+            }
+        }
+
+        // release args so they can be used by another token in the series
+        if series.params.enforce_unique_mint_args {
+            self.series_mint_arg_hashes
+                .remove(&hash_series_args(series_name, &token_data.series_args.mint));
+        }
+        if series.params.enforce_unique_owner_args && !token_data.series_args.owner.is_empty() {
+            self.series_owner_arg_hashes
+                .remove(&hash_series_args(series_name, &token_data.series_args.owner));
+        }
+
+        token_data
+    }
+
     pub(crate) fn internal_transfer(
         &mut self,
         sender_id: &AccountId,
@@ -110,6 +237,7 @@ impl Contract {
         approval_id: Option<U64>,
         memo: Option<String>,
     ) -> Token {
+        self.assert_not_paused(Feature::Transfers);
         let token = self.tokens_by_id.get(token_id).expect("Token not found");
 
         if sender_id != &token.owner_id && !token.approved_account_ids.contains_key(sender_id) {
@@ -134,13 +262,6 @@ impl Contract {
             "The token owner and the receiver should be different"
         );
 
-        log!(
-            "Transfer {} from @{} to @{}",
-            token_id,
-            &token.owner_id,
-            receiver_id
-        );
-
         self.internal_remove_token_from_owner(&token.owner_id, token_id);
         self.internal_add_token_to_owner(receiver_id, token_id);
 
@@ -151,9 +272,19 @@ impl Contract {
         };
         self.tokens_by_id.insert(token_id, &new_token);
 
-        if let Some(memo) = memo {
-            log!("Memo: {}", memo);
-        }
+        let authorized_id = if sender_id != &token.owner_id {
+            Some(sender_id.clone())
+        } else {
+            None
+        };
+        EventLogVariant::NftTransfer(vec![NftTransferLog {
+            authorized_id,
+            old_owner_id: token.owner_id.clone(),
+            new_owner_id: receiver_id.clone(),
+            token_ids: vec![token_id.clone()],
+            memo,
+        }])
+        .emit();
 
         token
     }
@@ -14,33 +14,49 @@ use near_sdk::{
 };
 
 use crate::internal::*;
+pub use crate::events::*;
 pub use crate::metadata::*;
+pub use crate::migrate::*;
 pub use crate::mint::*;
 pub use crate::nft_core::*;
+pub use crate::payout::*;
 pub use crate::token::*;
 
 // CUSTOM
 pub use crate::enumerable::*;
 pub use crate::package::*;
+pub use crate::params::*;
+pub use crate::pause::*;
+pub use crate::roles::*;
 pub use crate::series::*;
+pub use crate::storage::*;
 
+mod events;
 mod internal;
 mod metadata;
+mod migrate;
 mod mint;
 mod nft_core;
+mod payout;
 mod token;
 // CUSTOM
 mod enumerable;
 mod package;
+mod params;
+mod pause;
+mod roles;
 mod series;
+mod storage;
 
 pub type TypeSupplyCaps = HashMap<String, U64>;
 pub const CONTRACT_ROYALTY_CAP: u32 = 1000;
 pub const MINTER_ROYALTY_CAP: u32 = 2000;
 static SERIES_VARIANT_DELIMETER: &str = ":";
-static ARGS_DELIMETER: &str = ",";
 const GAS_FOR_SERIES_APPROVE: Gas = 20_000_000_000_000;
 const GAS_FOR_NFT_APPROVE: Gas = 10_000_000_000_000;
+const GAS_FOR_NFT_ON_REVOKE: Gas = 10_000_000_000_000;
+/// left for the rest of the call after nft_on_revoke notifications are scheduled
+const GAS_RESERVED_AFTER_REVOKE: Gas = 20_000_000_000_000;
 const GAS_FOR_RESOLVE_TRANSFER: Gas = 10_000_000_000_000;
 const GAS_FOR_NFT_TRANSFER_CALL: Gas = 25_000_000_000_000 + GAS_FOR_RESOLVE_TRANSFER;
 const NO_DEPOSIT: Balance = 0;
@@ -79,6 +95,14 @@ pub struct Contract {
     pub packages_by_name_version: UnorderedMap<PackageNameVersion, Package>,
     pub tokens_per_package: LookupMap<PackageNameVersion, UnorderedSet<TokenId>>,
     pub contract_royalty: u32,
+    pub storage_deposits: LookupMap<AccountId, Balance>,
+    pub paused_features: UnorderedSet<Feature>,
+    pub pending_owner_id: Option<AccountId>,
+    pub accounts_per_role: LookupMap<Role, UnorderedSet<AccountId>>,
+    pub owner_args_history_by_id: LookupMap<TokenId, Vec<OwnerArgsChange>>,
+    pub mint_phase_allowlists: LookupMap<SeriesName, UnorderedSet<CryptoHash>>,
+    pub record_migration: Option<RecordMigration>,
+    pub state_version: u16,
 }
 
 /// Helper structure to for keys of the persistent collections.
@@ -111,6 +135,17 @@ pub enum StorageKey {
     TokenPerPackageInner {
         package_name_version_hash: CryptoHash,
     },
+    StorageDeposits,
+    PausedFeatures,
+    AccountsPerRole,
+    AccountsPerRoleInner {
+        role: Role,
+    },
+    OwnerArgsHistoryById,
+    MintPhaseAllowlists,
+    MintPhaseAllowlistsInner {
+        series_name_hash: CryptoHash,
+    },
 }
 
 #[near_bindgen]
@@ -137,6 +172,14 @@ impl Contract {
             tokens_per_package: LookupMap::new(StorageKey::TokensPerPackage),
 
             contract_royalty: 0,
+            storage_deposits: LookupMap::new(StorageKey::StorageDeposits),
+            paused_features: UnorderedSet::new(StorageKey::PausedFeatures),
+            pending_owner_id: None,
+            accounts_per_role: LookupMap::new(StorageKey::AccountsPerRole),
+            owner_args_history_by_id: LookupMap::new(StorageKey::OwnerArgsHistoryById),
+            mint_phase_allowlists: LookupMap::new(StorageKey::MintPhaseAllowlists),
+            record_migration: None,
+            state_version: STATE_VERSION,
         };
 
         this.measure_min_token_storage_cost();
@@ -161,10 +204,10 @@ impl Contract {
         self.tokens_per_owner.remove(&tmp_account_id);
     }
 
-    /// CUSTOM - setters for owner
+    /// CUSTOM - setters for owner and roles
 
     pub fn set_contract_royalty(&mut self, contract_royalty: u32) {
-        self.assert_owner();
+        self.assert_role(Role::FeeManager);
         assert!(
             contract_royalty <= CONTRACT_ROYALTY_CAP,
             "Contract royalties limited to 10% for owner"
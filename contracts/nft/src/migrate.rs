@@ -0,0 +1,312 @@
+use crate::*;
+
+/// bump whenever the layout of `Contract` or of the stored `Series` and `TokenData` records
+/// changes, and add the previous layouts below. Version 1 is the layout before state versioning
+pub const STATE_VERSION: u16 = 2;
+
+/// layout before state versioning
+#[derive(BorshDeserialize)]
+pub struct ContractV1 {
+    pub tokens_per_owner: LookupMap<AccountId, UnorderedSet<TokenId>>,
+    pub tokens_by_id: UnorderedMap<TokenId, Token>,
+    pub owner_id: AccountId,
+    pub extra_storage_in_bytes_per_token: StorageUsage,
+    pub metadata: LazyOption<NFTMetadata>,
+    pub token_data_by_id: LookupMap<TokenId, TokenData>,
+    pub series_mint_arg_hashes: LookupSet<CryptoHash>,
+    pub series_owner_arg_hashes: LookupSet<CryptoHash>,
+    pub series_by_name: UnorderedMap<SeriesName, Series>,
+    pub series_per_owner: LookupMap<AccountId, UnorderedSet<SeriesName>>,
+    pub tokens_per_series: LookupMap<SeriesName, UnorderedSet<TokenId>>,
+    pub packages_by_name_version: UnorderedMap<PackageNameVersion, Package>,
+    pub tokens_per_package: LookupMap<PackageNameVersion, UnorderedSet<TokenId>>,
+    pub contract_royalty: u32,
+}
+
+/// series layout before state versioning, `Src` only gained a variant since
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct SeriesV1 {
+    pub series_name: String,
+    pub src: Src,
+    pub royalty: HashMap<AccountId, u32>,
+    pub owner_id: AccountId,
+    pub approved_account_ids: UnorderedSet<AccountId>,
+    pub created_at: U64,
+    pub params: SeriesParamsV1,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct SeriesParamsV1 {
+    pub max_supply: U64,
+    pub enforce_unique_mint_args: bool,
+    pub enforce_unique_owner_args: bool,
+    pub mint: Vec<String>,
+    pub owner: Vec<String>,
+    pub packages: Vec<String>,
+}
+
+/// token data layout before state versioning
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct TokenDataV1 {
+    pub metadata: TokenMetadataV1,
+    pub royalty: HashMap<AccountId, u32>,
+    pub series_args: SeriesArgs,
+    pub num_transfers: U64,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct TokenMetadataV1 {
+    pub media: Option<String>,
+    pub issued_at: Option<String>,
+}
+
+/// progress of rewriting the series and token records stored with the version 1 layout,
+/// series are migrated first, then tokens in tokens_by_id order
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RecordMigration {
+    pub series_index: U64,
+    pub token_index: U64,
+}
+
+/// the same collection read with a legacy value type, collection layouts don't depend on it
+fn legacy_collection<T: BorshSerialize, U: BorshDeserialize>(collection: &T) -> U {
+    U::try_from_slice(&collection.try_to_vec().unwrap()).unwrap()
+}
+
+pub enum VersionedContract {
+    V1(ContractV1),
+    Current(Contract),
+}
+
+impl VersionedContract {
+    /// reads the raw contract state and detects which layout it was written with
+    pub fn read() -> Self {
+        let state = env::storage_read(b"STATE").expect("Contract is not initialized");
+        if let Ok(contract) = Contract::try_from_slice(&state) {
+            return VersionedContract::Current(contract);
+        }
+        if let Ok(contract) = ContractV1::try_from_slice(&state) {
+            return VersionedContract::V1(contract);
+        }
+        env::panic(b"Unknown contract state layout")
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// only owner - call after deploying new code to upgrade existing state
+    #[init(ignore_state)]
+    pub fn migrate() -> Self {
+        let contract = match VersionedContract::read() {
+            VersionedContract::V1(old) => Contract {
+                tokens_per_owner: old.tokens_per_owner,
+                tokens_by_id: old.tokens_by_id,
+                owner_id: old.owner_id,
+                extra_storage_in_bytes_per_token: old.extra_storage_in_bytes_per_token,
+                metadata: old.metadata,
+                token_data_by_id: old.token_data_by_id,
+                series_mint_arg_hashes: old.series_mint_arg_hashes,
+                series_owner_arg_hashes: old.series_owner_arg_hashes,
+                series_by_name: old.series_by_name,
+                series_per_owner: old.series_per_owner,
+                tokens_per_series: old.tokens_per_series,
+                packages_by_name_version: old.packages_by_name_version,
+                tokens_per_package: old.tokens_per_package,
+                contract_royalty: old.contract_royalty,
+                storage_deposits: LookupMap::new(StorageKey::StorageDeposits),
+                paused_features: UnorderedSet::new(StorageKey::PausedFeatures),
+                pending_owner_id: None,
+                accounts_per_role: LookupMap::new(StorageKey::AccountsPerRole),
+                owner_args_history_by_id: LookupMap::new(StorageKey::OwnerArgsHistoryById),
+                mint_phase_allowlists: LookupMap::new(StorageKey::MintPhaseAllowlists),
+                record_migration: Some(RecordMigration {
+                    series_index: U64(0),
+                    token_index: U64(0),
+                }),
+                state_version: STATE_VERSION,
+            },
+            VersionedContract::Current(contract) => {
+                assert!(contract.state_version < STATE_VERSION, "Already migrated");
+                contract.assert_records_migrated();
+                Contract {
+                    state_version: STATE_VERSION,
+                    ..contract
+                }
+            }
+        };
+        contract.assert_owner();
+        contract
+    }
+
+    /// only owner - call after migrate until it returns true, rewrites up to limit series
+    /// and token records still stored with an older layout
+    pub fn migrate_records(&mut self, limit: u64) -> bool {
+        self.assert_owner();
+        let mut migration = match self.record_migration.take() {
+            Some(migration) => migration,
+            None => return true,
+        };
+        let mut remaining = limit;
+
+        let legacy_series: UnorderedMap<SeriesName, SeriesV1> =
+            legacy_collection(&self.series_by_name);
+        let num_series = legacy_series.len();
+        let end = min(migration.series_index.0 + remaining, num_series);
+        for i in migration.series_index.0..end {
+            let series_name = legacy_series.keys_as_vector().get(i).unwrap();
+            let series = self.migrate_series_v1(legacy_series.values_as_vector().get(i).unwrap());
+            self.series_by_name.insert(&series_name, &series);
+        }
+        remaining -= end - migration.series_index.0;
+        migration.series_index = U64(end);
+
+        let legacy_token_data: LookupMap<TokenId, TokenDataV1> =
+            legacy_collection(&self.token_data_by_id);
+        let num_tokens = self.tokens_by_id.len();
+        let end = min(migration.token_index.0 + remaining, num_tokens);
+        let token_ids: Vec<TokenId> = (migration.token_index.0..end)
+            .map(|i| self.tokens_by_id.keys_as_vector().get(i).unwrap())
+            .collect();
+        for token_id in token_ids {
+            if let Some(old) = legacy_token_data.get(&token_id) {
+                let token_data = migrate_token_data_v1(old);
+                self.migrate_arg_hashes_v1(&token_data.series_args);
+                self.token_data_by_id.insert(&token_id, &token_data);
+            }
+        }
+        migration.token_index = U64(end);
+
+        let done = migration.series_index.0 == num_series && migration.token_index.0 == num_tokens;
+        if !done {
+            self.record_migration = Some(migration);
+        }
+        done
+    }
+
+    fn migrate_series_v1(&mut self, old: SeriesV1) -> Series {
+        // baseline token ids were the series supply at mint and tokens could not be burned
+        let minted_count = self
+            .tokens_per_series
+            .get(&old.series_name)
+            .map_or(0, |tokens| tokens.len());
+
+        // series were stored under the lowercased name, but series_per_owner and the approvals
+        // prefix used the name as typed at creation
+        let series_name = &old.series_name;
+        let mut approved_account_ids = old.approved_account_ids;
+        if let Some(mut series_per_owner) = self.series_per_owner.get(&old.owner_id) {
+            let typed_names: Vec<SeriesName> = series_per_owner
+                .iter()
+                .filter(|name| name != series_name && &name.to_lowercase() == series_name)
+                .collect();
+            if !typed_names.is_empty() {
+                for name in typed_names.iter() {
+                    series_per_owner.remove(name);
+                }
+                series_per_owner.insert(&old.series_name);
+                self.series_per_owner.insert(&old.owner_id, &series_per_owner);
+
+                let mut canonical_approved_account_ids =
+                    UnorderedSet::new(StorageKey::SeriesApprovedIds {
+                        series_name_hash: hash_account_id(&old.series_name),
+                    });
+                canonical_approved_account_ids.extend(approved_account_ids.iter());
+                approved_account_ids.clear();
+                approved_account_ids = canonical_approved_account_ids;
+            }
+        }
+
+        Series {
+            series_name: old.series_name,
+            src: old.src,
+            src_hash: None,
+            royalty: old.royalty,
+            owner_id: old.owner_id,
+            approved_account_ids,
+            created_at: old.created_at,
+            params: SeriesParams {
+                max_supply: old.params.max_supply,
+                enforce_unique_mint_args: old.params.enforce_unique_mint_args,
+                enforce_unique_owner_args: old.params.enforce_unique_owner_args,
+                mint: old.params.mint,
+                owner: old.params.owner,
+                packages: old.params.packages,
+                mint_types: vec![],
+                owner_types: vec![],
+                derive_mint_args_from_seed: false,
+                phases: vec![],
+                mint_price: None,
+                reserved_supply: None,
+                owner_args_update_fee: None,
+                owner_args_update_cooldown: None,
+                max_owner_args_updates: None,
+                media_template: None,
+                reference_template: None,
+            },
+            phases_minted: vec![],
+            closed: false,
+            reserved_minted: U64(0),
+            minted_count: U64(minted_count),
+        }
+    }
+
+    /// rehashes args reserved with the unscoped version 1 scheme, name ++ args joined by ","
+    fn migrate_arg_hashes_v1(&mut self, series_args: &SeriesArgs) {
+        let series_name = &series_args.series_name;
+        let hash_v1 = |args: &[String]| hash_account_id(&format!("{}{}", series_name, args.join(",")));
+        if self.series_mint_arg_hashes.remove(&hash_v1(&series_args.mint)) {
+            self.series_mint_arg_hashes
+                .insert(&hash_series_args(series_name, &series_args.mint));
+        }
+        // version 1 only reserved owner args once the owner updated them
+        if self.series_owner_arg_hashes.remove(&hash_v1(&series_args.owner)) {
+            self.series_owner_arg_hashes
+                .insert(&hash_series_args(series_name, &series_args.owner));
+        }
+    }
+
+    /// views
+    pub fn get_state_version(&self) -> u16 {
+        self.state_version
+    }
+
+    pub fn get_record_migration(&self) -> Option<RecordMigration> {
+        self.record_migration.clone()
+    }
+}
+
+fn migrate_token_data_v1(old: TokenDataV1) -> TokenData {
+    TokenData {
+        metadata: TokenMetadata {
+            title: None,
+            description: None,
+            media: old.metadata.media,
+            media_hash: None,
+            copies: None,
+            issued_at: old.metadata.issued_at,
+            expires_at: None,
+            starts_at: None,
+            updated_at: None,
+            extra: None,
+            reference: None,
+            reference_hash: None,
+        },
+        royalty: old.royalty,
+        series_args: old.series_args,
+        num_transfers: old.num_transfers,
+        seed: None,
+        num_owner_args_updates: 0,
+        owner_args_updated_at: None,
+    }
+}
+
+impl Contract {
+    /// series and token records can't be read or removed until migrate_records is done
+    pub(crate) fn assert_records_migrated(&self) {
+        assert!(
+            self.record_migration.is_none(),
+            "Records must be migrated with migrate_records first"
+        );
+    }
+}
@@ -8,7 +8,7 @@ impl Contract {
         &mut self,
         series_mint_args: SeriesMintArgs,
     ) -> (TokenId, AccountId) {
-        let series_name = series_mint_args.series_name.clone();
+        let series_name = normalize_series_name(&series_mint_args.series_name);
         let series = self
             .series_by_name
             .get(&series_name)
@@ -19,20 +19,120 @@ impl Contract {
             "predecessor_account_id (market?) not approved to lazy mint series"
         );
 
-        self.internal_mint(series, series_mint_args)
+        let initial_storage_usage = env::storage_usage();
+        let minted = self.internal_mint(series, series_mint_args, false);
+
+        // refund unused deposit amount
+        self.internal_refund_deposit(initial_storage_usage, env::storage_usage() + self.extra_storage_in_bytes_per_token, None);
+
+        minted
+    }
+
+    /// CUSTOM - primary sale of a series at its mint_price, attached deposit covers price + storage
+    #[payable]
+    pub fn series_mint(&mut self, series_mint_args: SeriesMintArgs) -> TokenId {
+        let series_name = normalize_series_name(&series_mint_args.series_name);
+        let series = self
+            .series_by_name
+            .get(&series_name)
+            .unwrap_or_else(|| panic!("No series {}", series_name));
+        let mint_price = series.params.mint_price.expect("Series has no mint price").0;
+        let deposit = env::attached_deposit();
+        assert!(
+            deposit >= mint_price,
+            "Must attach at least the mint price of {}",
+            mint_price
+        );
+        let royalty = series.royalty.clone();
+
+        let initial_storage_usage = env::storage_usage();
+        let (token_id, series_owner_id) = self.internal_mint(series, series_mint_args, false);
+
+        let refund = self.internal_pay_storage(
+            deposit - mint_price,
+            env::storage_usage() + self.extra_storage_in_bytes_per_token - initial_storage_usage,
+        );
+        if refund > 1 {
+            Promise::new(env::predecessor_account_id()).transfer(refund);
+        }
+
+        // split proceeds between series royalties, contract royalty and the series owner
+        let payout = self.internal_payout(&series_owner_id, &royalty, mint_price, None);
+        for (receiver_id, amount) in payout.payout {
+            if amount.0 > 0 {
+                Promise::new(receiver_id).transfer(amount.0);
+            }
+        }
+
+        token_id
+    }
+
+    /// CUSTOM - owner airdrop, mints count against reserved_supply and skip mint phases,
+    /// storage for the whole batch is paid once, recipients are (receiver_id, mint, owner)
+    #[payable]
+    pub fn series_mint_batch(
+        &mut self,
+        series_name: String,
+        recipients: Vec<(ValidAccountId, Vec<String>, Vec<String>)>,
+    ) -> Vec<TokenId> {
+        assert_at_least_one_yocto();
+        let series_name = normalize_series_name(&series_name);
+        assert!(!recipients.is_empty(), "Must mint at least one token");
+        let series = self
+            .series_by_name
+            .get(&series_name)
+            .unwrap_or_else(|| panic!("No series {}", series_name));
+        assert_eq!(
+            series.owner_id,
+            env::predecessor_account_id(),
+            "Must be series owner"
+        );
+
+        let initial_storage_usage = env::storage_usage();
+        let num_recipients = recipients.len() as u64;
+        let token_ids = recipients
+            .into_iter()
+            .map(|(receiver_id, mint, owner)| {
+                // re-read, every mint updates the series counters
+                let series = self.series_by_name.get(&series_name).unwrap();
+                self.internal_mint(
+                    series,
+                    SeriesMintArgs {
+                        series_name: series_name.clone(),
+                        mint,
+                        owner,
+                        perpetual_royalties: None,
+                        receiver_id: Some(receiver_id),
+                        media: None,
+                    },
+                    true,
+                )
+                .0
+            })
+            .collect();
+
+        self.internal_refund_deposit(
+            initial_storage_usage,
+            env::storage_usage() + self.extra_storage_in_bytes_per_token * num_recipients,
+            None,
+        );
+
+        token_ids
     }
 
     fn internal_mint(
         &mut self,
-        series: Series,
+        mut series: Series,
         series_mint_args: SeriesMintArgs,
+        reserved: bool,
     ) -> (TokenId, AccountId) {
-        let initial_storage_usage = env::storage_usage();
+        self.assert_not_paused(Feature::Minting);
         let mut owner_id = env::predecessor_account_id();
 
+        let series_name = series.series_name.clone();
         let SeriesMintArgs {
-            series_name,
-            mint,
+            series_name: _,
+            mut mint,
             owner,
             perpetual_royalties,
             receiver_id,
@@ -44,25 +144,77 @@ impl Contract {
                 series_name_hash: hash_account_id(&series_name),
             })
         });
-        let num_tokens = tokens_per_series.len();
-        assert!(
-            num_tokens < series.params.max_supply.0,
-            "Cannot mint anymore of series: {}",
-            series_name
-        );
+        assert!(!series.closed, "Series {} is closed", series_name);
+        if reserved {
+            let reserved_supply = series.params.reserved_supply.map_or(0, |r| r.0);
+            assert!(
+                series.reserved_minted.0 < reserved_supply,
+                "Cannot mint anymore reserved tokens of series: {}",
+                series_name
+            );
+            series.reserved_minted = U64(series.reserved_minted.0 + 1);
+        } else {
+            assert!(
+                series.public_minted() < series.params.max_supply.0,
+                "Cannot mint anymore of series: {}",
+                series_name
+            );
+        }
 
         if let Some(receiver_id) = receiver_id {
             owner_id = receiver_id.into();
         }
-        let token_id = format!("{}{}{}", series_name, SERIES_VARIANT_DELIMETER, num_tokens);
+        // ids come from the counter, never tokens_per_series.len(), so burns can't cause reuse
+        let token_id = format!("{}{}{}", series_name, SERIES_VARIANT_DELIMETER, series.minted_count.0);
+        series.minted_count = U64(series.minted_count.0 + 1);
+
+        // CUSTOM - reproducible per token seed, optionally filling missing mint args from it
+        let seed = hex_sha256(&[env::random_seed(), token_id.as_bytes().to_vec()].concat());
+        if series.params.derive_mint_args_from_seed {
+            series.params.derive_mint_args(&seed, &mut mint);
+        }
+        series.params.assert_valid_mint_args(&mint);
+        // owner args left empty fall back to the series defaults until the owner sets them
+        if !owner.is_empty() {
+            series.params.assert_valid_owner_args(&owner);
+        }
+
+        // CUSTOM - enforce the active mint phase for the token receiver
+        if !reserved && !series.params.phases.is_empty() {
+            let index = series.active_phase_index().expect("No active mint phase");
+            let phase = &series.params.phases[index];
+            if let Some(max_supply) = phase.max_supply {
+                assert!(
+                    series.phases_minted[index].0 < max_supply.0,
+                    "Cannot mint anymore in this phase"
+                );
+            }
+            if phase.allowlist {
+                assert!(
+                    self.series_allowlists(&series_name)
+                        .contains(&series.allowlist_key(index as u32, &owner_id)),
+                    "Receiver not on mint phase allowlist"
+                );
+            }
+            series.phases_minted[index] = U64(series.phases_minted[index].0 + 1);
+        }
+        self.series_by_name.insert(&series_name, &series);
+
+        // minted_count is already incremented, so it is this token's 1 based edition
+        let metadata = series.token_metadata(&token_id, series.minted_count.0, &mint, media);
 
         if series.params.enforce_unique_mint_args {
-            let series_mint_arg_hash = hash_account_id(&format!("{}{}", series_name, mint.join(ARGS_DELIMETER)));
             assert!(
-                self.series_mint_arg_hashes.insert(&series_mint_arg_hash),
+                self.series_mint_arg_hashes.insert(&hash_series_args(&series_name, &mint)),
                 "Token in series has identical args"
             );
         }
+        if series.params.enforce_unique_owner_args && !owner.is_empty() {
+            assert!(
+                self.series_owner_arg_hashes.insert(&hash_series_args(&series_name, &owner)),
+                "Token in series has identical owner args"
+            );
+        }
 
         // insert everything
         tokens_per_series.insert(&token_id);
@@ -109,9 +261,6 @@ impl Contract {
             "Token already exists"
         );
 
-        // handle media
-        let media = media.map(|s| format!("{}{}.png", s, token_id));
-
         self.token_data_by_id.insert(
             &token_id,
             &TokenData {
@@ -122,16 +271,20 @@ impl Contract {
                 },
                 royalty,
                 num_transfers: U64(0),
-                metadata: TokenMetadata{
-                    media,
-                    issued_at: Some(env::block_timestamp().to_string())
-                },
+                seed: Some(seed),
+                num_owner_args_updates: 0,
+                owner_args_updated_at: None,
+                metadata,
             },
         );
         self.internal_add_token_to_owner(&token.owner_id, &token_id);
 
-        // refund unused deposit amount
-        refund_deposit(initial_storage_usage, env::storage_usage() + self.extra_storage_in_bytes_per_token, None);
+        EventLogVariant::NftMint(vec![NftMintLog {
+            owner_id: token.owner_id,
+            token_ids: vec![token_id.clone()],
+            memo: None,
+        }])
+        .emit();
 
         (token_id, series.owner_id)
     }
@@ -148,6 +301,7 @@ impl Contract {
             receiver_id,
             media,
         } = series_mint_args;
+        let series_name = normalize_series_name(&series_name);
 
         // CUSTOM - enforce series supply limit and store tokens per series / per package
         let series = self
@@ -160,11 +314,11 @@ impl Contract {
                 series_name_hash: hash_account_id(&series_name),
             })
         });
-        let num_tokens = tokens_per_series.len();
         if let Some(receiver_id) = receiver_id {
             owner_id = receiver_id.into();
         }
-        let token_id = format!("{}{}{}", series_name, SERIES_VARIANT_DELIMETER, num_tokens);
+        let token_id = format!("{}{}{}", series_name, SERIES_VARIANT_DELIMETER, series.minted_count.0);
+        let metadata = series.token_metadata(&token_id, series.minted_count.0 + 1, &mint, media);
 
         // insert everything
         tokens_per_series.insert(&token_id);
@@ -202,9 +356,6 @@ impl Contract {
             "Token already exists"
         );
 
-        // handle media
-        let media = media.map(|s| format!("{}{}.png", s, token_id));
-
         self.token_data_by_id.insert(
             &token_id,
             &TokenData {
@@ -215,10 +366,11 @@ impl Contract {
                 },
                 royalty,
                 num_transfers: U64(0),
-                metadata: TokenMetadata{
-                    media,
-                    issued_at: Some(env::block_timestamp().to_string())
-                },
+                // same length as a real seed
+                seed: Some(hex_sha256(token_id.as_bytes())),
+                num_owner_args_updates: 0,
+                owner_args_updated_at: None,
+                metadata,
             },
         );
         self.internal_add_token_to_owner(&token.owner_id, &token_id);
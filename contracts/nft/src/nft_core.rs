@@ -9,17 +9,6 @@ pub trait NonFungibleTokenCore {
         memo: Option<String>,
     );
 
-    fn nft_transfer_payout(
-        &mut self,
-        receiver_id: ValidAccountId,
-        token_id: TokenId,
-        approval_id: Option<U64>,
-        memo: Option<String>,
-        msg: Option<String>,
-        balance: Option<U128>,
-        max_len_payout: Option<u32>,
-    ) -> Option<Payout>;
-
     /// Returns `true` if the token was transferred from the sender's account.
     fn nft_transfer_call(
         &mut self,
@@ -36,6 +25,13 @@ pub trait NonFungibleTokenCore {
 
     fn nft_revoke_all(&mut self, token_id: TokenId);
 
+    fn nft_is_approved(
+        &self,
+        token_id: TokenId,
+        approved_account_id: ValidAccountId,
+        approval_id: Option<U64>,
+    ) -> bool;
+
     fn nft_total_supply(&self) -> U64;
 
     fn nft_token(&self, token_id: TokenId) -> Option<JsonToken>;
@@ -62,9 +58,9 @@ trait NonFungibleTokenApprovalsReceiver {
         approval_id: U64,
         msg: String,
     );
-}
 
-// TODO: create nft_on_revoke
+    fn nft_on_revoke(&mut self, token_id: TokenId);
+}
 
 #[ext_contract(ext_self)]
 trait NonFungibleTokenResolver {
@@ -112,106 +108,6 @@ impl NonFungibleTokenCore for Contract {
         );
     }
 
-    // CUSTOM - this method is included for marketplaces that respect royalties
-    #[payable]
-    fn nft_transfer_payout(
-        &mut self,
-        receiver_id: ValidAccountId,
-        token_id: TokenId,
-        approval_id: Option<U64>,
-        memo: Option<String>,
-        msg: Option<String>,
-        balance: Option<U128>,
-        max_len_payout: Option<u32>,
-    ) -> Option<Payout> {
-        assert_at_least_one_yocto();
-        let sender_id = env::predecessor_account_id();
-
-        // should mint token from series for specified receiver_id
-        let (owner_id, token_id, approved_account_ids) = if let Some(msg) = msg {
-            let series_mint_args: SeriesMintArgs =
-                near_sdk::serde_json::from_str(&msg).expect("Invalid SeriesMintArgs");
-            let (new_token_id, series_owner_id) = self.lazy_mint(series_mint_args);
-            (series_owner_id, new_token_id, HashMap::default())
-        } else {
-            let previous_token = self.internal_transfer(
-                &sender_id,
-                receiver_id.as_ref(),
-                &token_id,
-                approval_id,
-                None,
-            );
-            (
-                previous_token.owner_id,
-                token_id,
-                previous_token.approved_account_ids,
-            )
-        };
-
-        let mut token_data = self.token_data_by_id.get(&token_id).expect("No token data");
-
-        // compute payouts based on balance option
-        // adds in contract_royalty and computes previous owner royalty from remainder
-
-        let mut total_perpetual = 0;
-        let payout = if let Some(balance) = balance {
-            let royalty = &token_data.royalty;
-
-            if let Some(max_len_payout) = max_len_payout {
-                assert!(
-                    royalty.len() as u32 <= max_len_payout,
-                    "Market cannot payout to that many receivers"
-                );
-            }
-
-            let balance_u128 = u128::from(balance);
-            let mut payout: Payout = HashMap::new();
-            for (k, v) in royalty.iter() {
-                let key = k.clone();
-                if key != owner_id {
-                    payout.insert(key, royalty_to_payout(*v, balance_u128));
-                    total_perpetual += *v;
-                }
-            }
-            // payout to contract owner - may be previous token owner -> then they get remainder of balance
-            if self.contract_royalty > 0 && self.owner_id != owner_id {
-                payout.insert(
-                    self.owner_id.clone(),
-                    royalty_to_payout(self.contract_royalty, balance_u128),
-                );
-                total_perpetual += self.contract_royalty;
-            }
-            assert!(
-                total_perpetual <= MINTER_ROYALTY_CAP + CONTRACT_ROYALTY_CAP,
-                "Royalties should not be more than caps"
-            );
-            // payout to previous owner
-            payout.insert(
-                owner_id.clone(),
-                royalty_to_payout(10000 - total_perpetual, balance_u128),
-            );
-
-            log!("total_perpetual {:?}", total_perpetual);
-            log!("Payouts {:?}", payout);
-
-            Some(payout)
-        } else {
-            None
-        };
-
-        token_data.num_transfers = U64(token_data.num_transfers.0 + 1);
-        self.token_data_by_id.insert(&token_id, &token_data);
-
-        if let Some(memo) = memo {
-            log!("Memo: {}", memo);
-        }
-
-        // refund any NEAR if storage reqs changed
-        refund_approved_account_ids(owner_id, &approved_account_ids);
-
-        payout
-    }
-
     #[payable]
     fn nft_transfer_call(
         &mut self,
@@ -271,7 +167,10 @@ impl NonFungibleTokenCore for Contract {
         token.next_approval_id += 1;
         self.tokens_by_id.insert(&token_id, &token);
 
-        let storage_cost = env::storage_byte_cost() * Balance::from(env::storage_usage() - initial_storage_usage);
+        let deposit = self.internal_pay_storage(
+            env::attached_deposit(),
+            env::storage_usage() - initial_storage_usage,
+        );
 
         if let Some(msg) = msg {
             ext_non_fungible_approval_receiver::nft_on_approve(
@@ -280,11 +179,11 @@ impl NonFungibleTokenCore for Contract {
                 approval_id,
                 msg,
                 &account_id,
-                env::attached_deposit()
-                    .checked_sub(storage_cost)
-                    .expect("Deposit not enough for approval"),
+                deposit,
                 env::prepaid_gas() - GAS_FOR_NFT_APPROVE,
             );
+        } else if deposit > 1 {
+            Promise::new(token.owner_id).transfer(deposit);
         }
     }
 
@@ -299,8 +198,9 @@ impl NonFungibleTokenCore for Contract {
             .remove(account_id.as_ref())
             .is_some()
         {
-            refund_approved_account_ids_iter(predecessor_account_id, [account_id.into()].iter());
+            refund_approved_account_ids_iter(predecessor_account_id, [account_id.clone().into()].iter());
             self.tokens_by_id.insert(&token_id, &token);
+            notify_revoked(&token_id, Some(account_id.into()));
         }
     }
 
@@ -312,11 +212,27 @@ impl NonFungibleTokenCore for Contract {
         assert_eq!(&predecessor_account_id, &token.owner_id);
         if !token.approved_account_ids.is_empty() {
             refund_approved_account_ids(predecessor_account_id, &token.approved_account_ids);
+            notify_revoked(&token_id, token.approved_account_ids.keys().cloned());
             token.approved_account_ids.clear();
             self.tokens_by_id.insert(&token_id, &token);
         }
     }
 
+    fn nft_is_approved(
+        &self,
+        token_id: TokenId,
+        approved_account_id: ValidAccountId,
+        approval_id: Option<U64>,
+    ) -> bool {
+        let token = self.tokens_by_id.get(&token_id).expect("Token not found");
+        match token.approved_account_ids.get(approved_account_id.as_ref()) {
+            Some(actual_approval_id) => approval_id
+                .map(|approval_id| &approval_id == actual_approval_id)
+                .unwrap_or(true),
+            None => false,
+        }
+    }
+
     fn nft_total_supply(&self) -> U64 {
         self.tokens_by_id.len().into()
     }
@@ -331,10 +247,9 @@ impl NonFungibleTokenCore for Contract {
                 series_args: token_data.series_args,
                 royalty: token_data.royalty,
                 num_transfers: token_data.num_transfers,
-                metadata: TokenMetadata{
-                    media: token_data.metadata.media,
-                    issued_at: token_data.metadata.issued_at
-                }
+                seed: token_data.seed,
+                num_owner_args_updates: token_data.num_owner_args_updates,
+                metadata: token_data.metadata,
             }
         })
     }
@@ -375,10 +290,18 @@ impl NonFungibleTokenResolver for Contract {
             return true;
         };
 
-        log!("Return {} from @{} to @{}", token_id, receiver_id, owner_id);
-
         self.internal_remove_token_from_owner(&receiver_id, &token_id);
         self.internal_add_token_to_owner(&owner_id, &token_id);
+
+        EventLogVariant::NftTransfer(vec![NftTransferLog {
+            authorized_id: None,
+            old_owner_id: receiver_id.clone(),
+            new_owner_id: owner_id.clone(),
+            token_ids: vec![token_id.clone()],
+            memo: None,
+        }])
+        .emit();
+
         token.owner_id = owner_id;
         refund_approved_account_ids(receiver_id, &token.approved_account_ids);
         token.approved_account_ids = approved_account_ids;
@@ -28,6 +28,7 @@ impl Contract {
         urls: Vec<String>,
     ) {
         assert_at_least_one_yocto();
+        self.assert_not_paused(Feature::PackagePublishing);
         assert!(name_version.contains(PACKAGE_NAME_VERSION_DELIMETER), "Package name_version must be <package>@<version>");
         let mut name_version_split = name_version.split(PACKAGE_NAME_VERSION_DELIMETER);
         assert!(!name_version_split.next().unwrap().is_empty(), "Must specify a package name");
@@ -40,7 +41,7 @@ impl Contract {
             urls,
         });
 
-        refund_deposit(initial_storage_usage, env::storage_usage(), None);
+        self.internal_refund_deposit(initial_storage_usage, env::storage_usage(), None);
     }
 
     #[payable]
@@ -50,12 +51,13 @@ impl Contract {
         urls: Vec<String>,
     ) {
         assert_at_least_one_yocto();
+        self.assert_not_paused(Feature::PackagePublishing);
         let initial_storage_usage = env::storage_usage();
     
         let mut package = self.packages_by_name_version.get(&name_version).unwrap_or_else(|| panic!("No package {}", name_version));
         package.urls.extend(urls);
 
-        refund_deposit(initial_storage_usage, env::storage_usage(), None);
+        self.internal_refund_deposit(initial_storage_usage, env::storage_usage(), None);
     }
 
     /// views
@@ -0,0 +1,196 @@
+use crate::*;
+
+/// type of a single series mint or owner arg, aligned by index with the param names
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ParamType {
+    Any,
+    Int { min: Option<i64>, max: Option<i64> },
+    Float { min: Option<f64>, max: Option<f64> },
+    /// hex color, "#rrggbb" or "0xrrggbb" with optional alpha
+    Color,
+    Enum { values: Vec<String> },
+    Bool,
+    String { max_len: u32 },
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ParamSchema {
+    pub name: String,
+    #[serde(flatten)]
+    pub param_type: ParamType,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SeriesParamsSchema {
+    pub mint: Vec<ParamSchema>,
+    pub owner: Vec<ParamSchema>,
+}
+
+/// args are stored as the strings the frontend sends, string-like values may be JSON quoted
+fn unquote(value: &str) -> &str {
+    value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .unwrap_or(value)
+}
+
+fn in_range<T: PartialOrd>(value: T, min: Option<T>, max: Option<T>) -> bool {
+    !matches!(min, Some(min) if value < min) && !matches!(max, Some(max) if value > max)
+}
+
+impl ParamType {
+    pub fn is_valid(&self, value: &str) -> bool {
+        let value = value.trim();
+        match self {
+            ParamType::Any => true,
+            ParamType::Int { min, max } => match value.parse::<i64>() {
+                Ok(v) => in_range(v, *min, *max),
+                Err(_) => false,
+            },
+            ParamType::Float { min, max } => match unquote(value).parse::<f64>() {
+                Ok(v) => v.is_finite() && in_range(v, *min, *max),
+                Err(_) => false,
+            },
+            ParamType::Color => {
+                let value = unquote(value);
+                let hex = value
+                    .strip_prefix('#')
+                    .or_else(|| value.strip_prefix("0x"))
+                    .unwrap_or("");
+                (hex.len() == 6 || hex.len() == 8) && hex.chars().all(|c| c.is_ascii_hexdigit())
+            }
+            ParamType::Enum { values } => values.iter().any(|v| v == unquote(value)),
+            ParamType::Bool => value == "true" || value == "false",
+            ParamType::String { max_len } => unquote(value).len() <= *max_len as usize,
+        }
+    }
+
+    /// a type no value can pass would leave the series unmintable
+    fn assert_valid(&self, name: &str) {
+        let satisfiable = match self {
+            ParamType::Int { min, max } => {
+                !matches!((min, max), (Some(min), Some(max)) if min > max)
+            }
+            ParamType::Float { min, max } => {
+                !matches!((min, max), (Some(min), Some(max)) if min > max)
+            }
+            ParamType::Enum { values } => !values.is_empty(),
+            ParamType::String { max_len } => *max_len > 0,
+            _ => true,
+        };
+        assert!(satisfiable, "Invalid type for param {}", name);
+    }
+
+    /// deterministic value of this type from a hash, in the format is_valid accepts
+    fn derive_value(&self, hash: &[u8]) -> String {
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(&hash[..8]);
+        let n = u64::from_le_bytes(bytes);
+        match self {
+            ParamType::Int { min, max } => {
+                let min = i128::from(min.unwrap_or(0));
+                let range = (i128::from(max.unwrap_or(i64::MAX)) - min + 1).max(1) as u128;
+                (min + (u128::from(n) % range) as i128).to_string()
+            }
+            ParamType::Float { min, max } => {
+                let min = min.unwrap_or(0.0);
+                let max = max.unwrap_or(1.0);
+                (min + (n as f64 / u64::MAX as f64) * (max - min)).to_string()
+            }
+            ParamType::Color => format!("\"0x{:02x}{:02x}{:02x}\"", hash[0], hash[1], hash[2]),
+            ParamType::Enum { values } if !values.is_empty() => {
+                format!("\"{}\"", values[(n % values.len() as u64) as usize])
+            }
+            ParamType::Bool => (n % 2 == 1).to_string(),
+            ParamType::String { max_len } => {
+                let mut value = hex_encode(hash);
+                value.truncate(*max_len as usize);
+                format!("\"{}\"", value)
+            }
+            _ => format!("\"{}\"", hex_encode(hash)),
+        }
+    }
+}
+
+impl SeriesParams {
+    pub(crate) fn assert_valid_schema(&self) {
+        assert!(
+            self.mint_types.is_empty() || self.mint_types.len() == self.mint.len(),
+            "mint_types must match mint params"
+        );
+        assert!(
+            self.owner_types.is_empty() || self.owner_types.len() == self.owner.len(),
+            "owner_types must match owner params"
+        );
+        for (name, param_type) in self
+            .mint
+            .iter()
+            .zip(&self.mint_types)
+            .chain(self.owner.iter().zip(&self.owner_types))
+        {
+            param_type.assert_valid(name);
+        }
+    }
+
+    pub(crate) fn assert_valid_mint_args(&self, args: &[String]) {
+        assert_valid_args(&self.mint, &self.mint_types, args);
+    }
+
+    pub(crate) fn assert_valid_owner_args(&self, args: &[String]) {
+        assert_valid_args(&self.owner, &self.owner_types, args);
+    }
+
+    /// appends a seed derived value for every mint param the minter did not supply
+    pub(crate) fn derive_mint_args(&self, seed: &str, args: &mut Vec<String>) {
+        for (i, name) in self.mint.iter().enumerate().skip(args.len()) {
+            let param_type = self.mint_types.get(i).cloned().unwrap_or(ParamType::Any);
+            let hash = env::sha256(format!("{}{}", seed, name).as_bytes());
+            args.push(param_type.derive_value(&hash));
+        }
+    }
+}
+
+fn assert_valid_args(names: &[String], types: &[ParamType], args: &[String]) {
+    if types.is_empty() {
+        return;
+    }
+    assert_eq!(types.len(), args.len(), "Incorrect number of args for series");
+    for ((name, param_type), value) in names.iter().zip(types).zip(args) {
+        assert!(
+            param_type.is_valid(value),
+            "Invalid value for param {}: {}",
+            name,
+            value
+        );
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// views
+    pub fn series_params_schema(&self, series_name: SeriesName) -> SeriesParamsSchema {
+        let series_name = normalize_series_name(&series_name);
+        let series = self
+            .series_by_name
+            .get(&series_name)
+            .unwrap_or_else(|| panic!("No series {}", series_name));
+        let schema = |names: &[String], types: &[ParamType]| {
+            names
+                .iter()
+                .enumerate()
+                .map(|(i, name)| ParamSchema {
+                    name: name.clone(),
+                    param_type: types.get(i).cloned().unwrap_or(ParamType::Any),
+                })
+                .collect()
+        };
+        SeriesParamsSchema {
+            mint: schema(&series.params.mint, &series.params.mint_types),
+            owner: schema(&series.params.owner, &series.params.owner_types),
+        }
+    }
+}
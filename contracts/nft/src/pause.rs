@@ -0,0 +1,45 @@
+use crate::*;
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+pub enum Feature {
+    Minting,
+    Transfers,
+    SeriesCreation,
+    PackagePublishing,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// only owner or pauser
+    #[payable]
+    pub fn set_feature_paused(&mut self, feature: Feature, paused: bool) {
+        assert_one_yocto();
+        self.assert_role(Role::Pauser);
+        if paused {
+            self.paused_features.insert(&feature);
+        } else {
+            self.paused_features.remove(&feature);
+        }
+    }
+
+    /// views
+    pub fn paused_features(&self) -> Vec<Feature> {
+        self.paused_features.to_vec()
+    }
+
+    pub fn is_feature_paused(&self, feature: Feature) -> bool {
+        self.paused_features.contains(&feature)
+    }
+}
+
+impl Contract {
+    /// also stops every feature while records are still being migrated
+    pub(crate) fn assert_not_paused(&self, feature: Feature) {
+        self.assert_records_migrated();
+        if self.paused_features.contains(&feature) {
+            env::panic(format!("{:?} is paused", feature).as_bytes());
+        }
+    }
+}
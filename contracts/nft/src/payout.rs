@@ -0,0 +1,127 @@
+use crate::*;
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Payout {
+    pub payout: HashMap<AccountId, U128>,
+}
+
+pub trait NonFungibleTokenPayout {
+    fn nft_payout(&self, token_id: TokenId, balance: U128, max_len_payout: Option<u32>) -> Payout;
+
+    fn nft_transfer_payout(
+        &mut self,
+        receiver_id: ValidAccountId,
+        token_id: TokenId,
+        approval_id: Option<u64>,
+        memo: Option<String>,
+        balance: U128,
+        max_len_payout: Option<u32>,
+    ) -> Payout;
+}
+
+#[near_bindgen]
+impl NonFungibleTokenPayout for Contract {
+    fn nft_payout(&self, token_id: TokenId, balance: U128, max_len_payout: Option<u32>) -> Payout {
+        let token = self.tokens_by_id.get(&token_id).expect("Token not found");
+        let token_data = self.token_data_by_id.get(&token_id).expect("No token data");
+        self.internal_payout(&token.owner_id, &token_data.royalty, balance.0, max_len_payout)
+    }
+
+    #[payable]
+    fn nft_transfer_payout(
+        &mut self,
+        receiver_id: ValidAccountId,
+        token_id: TokenId,
+        approval_id: Option<u64>,
+        memo: Option<String>,
+        balance: U128,
+        max_len_payout: Option<u32>,
+    ) -> Payout {
+        assert_one_yocto();
+        let sender_id = env::predecessor_account_id();
+        let previous_token = self.internal_transfer(
+            &sender_id,
+            receiver_id.as_ref(),
+            &token_id,
+            approval_id.map(U64),
+            memo,
+        );
+        refund_approved_account_ids(
+            previous_token.owner_id.clone(),
+            &previous_token.approved_account_ids,
+        );
+
+        let mut token_data = self.token_data_by_id.get(&token_id).expect("No token data");
+        token_data.num_transfers = U64(token_data.num_transfers.0 + 1);
+        self.token_data_by_id.insert(&token_id, &token_data);
+
+        self.internal_payout(&previous_token.owner_id, &token_data.royalty, balance.0, max_len_payout)
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// CUSTOM - lazy mint a token from a series for receiver_id, series owner is paid as the seller
+    #[payable]
+    pub fn lazy_mint_payout(
+        &mut self,
+        receiver_id: ValidAccountId,
+        series_mint_args: SeriesMintArgs,
+        balance: U128,
+        max_len_payout: Option<u32>,
+    ) -> Payout {
+        assert_at_least_one_yocto();
+        let (token_id, series_owner_id) = self.lazy_mint(SeriesMintArgs {
+            receiver_id: Some(receiver_id),
+            ..series_mint_args
+        });
+
+        let mut token_data = self.token_data_by_id.get(&token_id).expect("No token data");
+        token_data.num_transfers = U64(token_data.num_transfers.0 + 1);
+        self.token_data_by_id.insert(&token_id, &token_data);
+
+        self.internal_payout(&series_owner_id, &token_data.royalty, balance.0, max_len_payout)
+    }
+
+    /// adds in contract_royalty and pays owner_id (seller) the remainder, including any
+    /// rounding dust, so the payout always sums exactly to balance
+    pub(crate) fn internal_payout(
+        &self,
+        owner_id: &AccountId,
+        royalty: &HashMap<AccountId, u32>,
+        balance: Balance,
+        max_len_payout: Option<u32>,
+    ) -> Payout {
+        let mut total_perpetual = 0;
+        let mut payout: HashMap<AccountId, U128> = HashMap::new();
+        for (k, v) in royalty.iter() {
+            if k != owner_id {
+                payout.insert(k.clone(), royalty_to_payout(*v, balance));
+                total_perpetual += *v;
+            }
+        }
+        // payout to contract owner - may be previous token owner -> then they get remainder of balance
+        if self.contract_royalty > 0 && &self.owner_id != owner_id {
+            let contract_payout = royalty_to_payout(self.contract_royalty, balance);
+            payout.entry(self.owner_id.clone()).or_insert(U128(0)).0 += contract_payout.0;
+            total_perpetual += self.contract_royalty;
+        }
+        assert!(
+            total_perpetual <= MINTER_ROYALTY_CAP + CONTRACT_ROYALTY_CAP,
+            "Royalties should not be more than caps"
+        );
+        // payout to previous owner
+        let total_royalties: Balance = payout.values().map(|amount| amount.0).sum();
+        payout.insert(owner_id.clone(), U128(balance - total_royalties));
+
+        if let Some(max_len_payout) = max_len_payout {
+            assert!(
+                payout.len() as u32 <= max_len_payout,
+                "Market cannot payout to that many receivers"
+            );
+        }
+
+        Payout { payout }
+    }
+}
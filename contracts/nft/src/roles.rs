@@ -0,0 +1,122 @@
+use crate::*;
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, Debug)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    Admin,
+    Pauser,
+    Curator,
+    FeeManager,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// only owner - new owner must call accept_ownership, None cancels a pending transfer
+    #[payable]
+    pub fn transfer_ownership(&mut self, new_owner_id: Option<ValidAccountId>) {
+        assert_one_yocto();
+        self.assert_owner();
+        self.pending_owner_id = new_owner_id.map(|a| a.into());
+    }
+
+    #[payable]
+    pub fn accept_ownership(&mut self) {
+        assert_one_yocto();
+        let predecessor_account_id = env::predecessor_account_id();
+        assert_eq!(
+            self.pending_owner_id.as_ref(),
+            Some(&predecessor_account_id),
+            "Must be pending owner"
+        );
+        self.owner_id = predecessor_account_id;
+        self.pending_owner_id = None;
+    }
+
+    /// owner grants any role, admins grant all roles except admin
+    #[payable]
+    pub fn grant_role(&mut self, role: Role, account_id: ValidAccountId) -> bool {
+        assert_one_yocto();
+        self.assert_role_manager(role);
+        let mut accounts = self.accounts_per_role.get(&role).unwrap_or_else(|| {
+            UnorderedSet::new(StorageKey::AccountsPerRoleInner { role })
+        });
+        let granted = accounts.insert(account_id.as_ref());
+        self.accounts_per_role.insert(&role, &accounts);
+        granted
+    }
+
+    #[payable]
+    pub fn revoke_role(&mut self, role: Role, account_id: ValidAccountId) -> bool {
+        assert_one_yocto();
+        self.assert_role_manager(role);
+        self.internal_remove_role(role, account_id.as_ref())
+    }
+
+    #[payable]
+    pub fn renounce_role(&mut self, role: Role) -> bool {
+        assert_one_yocto();
+        self.internal_remove_role(role, &env::predecessor_account_id())
+    }
+
+    /// views
+    pub fn get_owner_id(&self) -> AccountId {
+        self.owner_id.clone()
+    }
+
+    pub fn get_pending_owner_id(&self) -> Option<AccountId> {
+        self.pending_owner_id.clone()
+    }
+
+    pub fn has_role(&self, role: Role, account_id: ValidAccountId) -> bool {
+        self.internal_has_role(role, account_id.as_ref())
+    }
+
+    pub fn get_role_members(&self, role: Role) -> Vec<AccountId> {
+        self.accounts_per_role
+            .get(&role)
+            .map(|accounts| accounts.to_vec())
+            .unwrap_or_default()
+    }
+}
+
+impl Contract {
+    /// owner implicitly holds every role
+    pub(crate) fn assert_role(&self, role: Role) {
+        let predecessor_account_id = env::predecessor_account_id();
+        if predecessor_account_id != self.owner_id
+            && !self.internal_has_role(role, &predecessor_account_id)
+        {
+            env::panic(format!("Requires {:?} role", role).as_bytes());
+        }
+    }
+
+    fn assert_role_manager(&self, role: Role) {
+        match role {
+            Role::Admin => self.assert_owner(),
+            _ => self.assert_role(Role::Admin),
+        }
+    }
+
+    fn internal_has_role(&self, role: Role, account_id: &AccountId) -> bool {
+        self.accounts_per_role
+            .get(&role)
+            .map(|accounts| accounts.contains(account_id))
+            .unwrap_or(false)
+    }
+
+    fn internal_remove_role(&mut self, role: Role, account_id: &AccountId) -> bool {
+        let mut accounts = if let Some(accounts) = self.accounts_per_role.get(&role) {
+            accounts
+        } else {
+            return false;
+        };
+        let removed = accounts.remove(account_id);
+        if accounts.is_empty() {
+            self.accounts_per_role.remove(&role);
+        } else {
+            self.accounts_per_role.insert(&role, &accounts);
+        }
+        removed
+    }
+}
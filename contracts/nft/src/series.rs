@@ -2,6 +2,32 @@ use crate::*;
 
 pub type SeriesName = String;
 
+pub const MAX_SERIES_NAME_LEN: usize = 64;
+
+/// lowercases series_name and panics unless it is 1-64 chars of [a-z0-9_-], so it can never
+/// contain SERIES_VARIANT_DELIMETER, only applied when a series is created
+pub(crate) fn canonical_series_name(series_name: &str) -> SeriesName {
+    let series_name = series_name.to_lowercase();
+    assert!(
+        !series_name.is_empty() && series_name.len() <= MAX_SERIES_NAME_LEN,
+        "Series name must be 1 to {} characters",
+        MAX_SERIES_NAME_LEN
+    );
+    assert!(
+        series_name
+            .chars()
+            .all(|c| matches!(c, 'a'..='z' | '0'..='9' | '_' | '-')),
+        "Series name may only contain a-z, 0-9, _ and -"
+    );
+    series_name
+}
+
+/// lowercases series_name for lookups without validating it, series created before names were
+/// validated may contain any characters
+pub(crate) fn normalize_series_name(series_name: &str) -> SeriesName {
+    series_name.to_lowercase()
+}
+
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
 #[serde(crate = "near_sdk::serde")]
 pub struct SeriesMintArgs {
@@ -30,12 +56,75 @@ pub struct SeriesParams {
     pub mint: Vec<String>,
     pub owner: Vec<String>,
     pub packages: Vec<String>,
+    #[serde(default)]
+    pub mint_types: Vec<ParamType>,
+    #[serde(default)]
+    pub owner_types: Vec<ParamType>,
+    /// mint args left out by the minter are derived from the token seed
+    #[serde(default)]
+    pub derive_mint_args_from_seed: bool,
+    #[serde(default)]
+    pub phases: Vec<MintPhase>,
+    /// price in yoctoNEAR for series_mint, None disables direct minting
+    #[serde(default)]
+    pub mint_price: Option<U128>,
+    /// tokens only the series owner can mint with series_mint_batch, on top of max_supply
+    #[serde(default)]
+    pub reserved_supply: Option<U64>,
+    /// yoctoNEAR paid on every update_token_owner_args, split over the series royalty with the
+    /// rest to the series owner
+    #[serde(default)]
+    pub owner_args_update_fee: Option<U128>,
+    /// nanoseconds a token must wait between owner args updates
+    #[serde(default)]
+    pub owner_args_update_cooldown: Option<U64>,
+    #[serde(default)]
+    pub max_owner_args_updates: Option<u32>,
+    /// token media and reference urls, placeholders {series_name} {edition} {token_id} {args}
+    /// are replaced at mint, edition starts at 1 and args are the mint args joined by ","
+    #[serde(default)]
+    pub media_template: Option<String>,
+    #[serde(default)]
+    pub reference_template: Option<String>,
+}
+
+/// a window in which the series can be minted, bounds are block timestamps in nanoseconds
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MintPhase {
+    pub starts_at: Option<U64>,
+    pub ends_at: Option<U64>,
+    pub max_supply: Option<U64>,
+    /// only accounts added with series_allowlist_add can receive tokens in this phase
+    #[serde(default)]
+    pub allowlist: bool,
+}
+
+impl MintPhase {
+    fn has_started(&self, now: u64) -> bool {
+        !matches!(self.starts_at, Some(t) if t.0 > now)
+    }
+
+    fn is_active(&self, now: u64) -> bool {
+        self.has_started(now) && !matches!(self.ends_at, Some(t) if t.0 <= now)
+    }
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ActiveMintPhase {
+    pub index: u32,
+    pub phase: MintPhase,
+    pub minted: U64,
+    pub remaining: U64,
 }
 
 #[derive(BorshDeserialize, BorshSerialize)]
 pub enum Src {
     Code(String),
     Bytes(U64),
+    /// chunks uploaded so far against the declared byte budget, not yet finalized
+    Partial { bytes: U64, code: String },
 }
 
 impl Serialize for Src {
@@ -51,17 +140,114 @@ impl Serialize for Src {
     }
 }
 
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SeriesArgsAvailable {
+    pub mint: bool,
+    pub owner: bool,
+}
+
 #[derive(BorshDeserialize, BorshSerialize, Serialize)]
 #[serde(crate = "near_sdk::serde")]
 pub struct Series {
     pub series_name: String,
     pub src: Src,
+    /// hex sha256 of src committed at creation, None for series created before src_hash
+    pub src_hash: Option<String>,
     pub royalty: HashMap<AccountId, u32>,
     pub owner_id: AccountId,
     #[serde(with = "unordered_set_json")]
     pub approved_account_ids: UnorderedSet<AccountId>,
     pub created_at: U64,
     pub params: SeriesParams,
+    pub phases_minted: Vec<U64>,
+    pub closed: bool,
+    pub reserved_minted: U64,
+    /// every token ever minted including burned ones, only increases. Token ids are
+    /// series_name:index with a 0 based index, while editions in titles start at 1
+    pub minted_count: U64,
+}
+
+/// series view with the number of tokens currently in existence, token series_name:n is
+/// edition n + 1 of minted_count
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct JsonSeries {
+    #[serde(flatten)]
+    pub series: Series,
+    pub current_supply: U64,
+}
+
+impl Series {
+    /// NEP-177 metadata for a new token, media_template takes precedence over the media base url
+    /// passed in SeriesMintArgs
+    pub(crate) fn token_metadata(
+        &self,
+        token_id: &str,
+        edition: u64,
+        mint: &[String],
+        media: Option<String>,
+    ) -> TokenMetadata {
+        let render = |template: &String| {
+            template
+                .replace("{series_name}", &self.series_name)
+                .replace("{edition}", &edition.to_string())
+                .replace("{token_id}", token_id)
+                .replace("{args}", &mint.join(","))
+        };
+        TokenMetadata {
+            title: Some(format!("{} #{}", self.series_name, edition)),
+            description: None,
+            media: self
+                .params
+                .media_template
+                .as_ref()
+                .map(&render)
+                .or_else(|| media.map(|s| format!("{}{}.png", s, token_id))),
+            media_hash: None,
+            copies: Some(U64(
+                self.params.max_supply.0 + self.params.reserved_supply.map_or(0, |r| r.0),
+            )),
+            issued_at: Some(env::block_timestamp().to_string()),
+            expires_at: None,
+            starts_at: None,
+            updated_at: None,
+            extra: None,
+            reference: self.params.reference_template.as_ref().map(&render),
+            reference_hash: None,
+        }
+    }
+
+    /// minted_count excluding reserved mints, what max_supply caps
+    pub(crate) fn public_minted(&self) -> u64 {
+        self.minted_count.0 - self.reserved_minted.0
+    }
+
+    /// first configured phase open at the current block timestamp
+    pub(crate) fn active_phase_index(&self) -> Option<usize> {
+        let now = env::block_timestamp();
+        self.params.phases.iter().position(|phase| phase.is_active(now))
+    }
+
+    /// key of account_id in the allowlist of a mint phase, kept in the series allowlists that
+    /// series_delete clears
+    pub(crate) fn allowlist_key(&self, phase_index: u32, account_id: &AccountId) -> CryptoHash {
+        let mut bytes = self.series_name.try_to_vec().unwrap();
+        bytes.extend_from_slice(&self.created_at.0.to_le_bytes());
+        bytes.extend_from_slice(&phase_index.to_le_bytes());
+        bytes.extend(account_id.try_to_vec().unwrap());
+        let mut hash = CryptoHash::default();
+        hash.copy_from_slice(&env::sha256(&bytes));
+        hash
+    }
+}
+
+fn assert_valid_phases(phases: &[MintPhase]) {
+    for phase in phases {
+        if let (Some(starts_at), Some(ends_at)) = (phase.starts_at, phase.ends_at) {
+            assert!(starts_at.0 < ends_at.0, "Mint phase must end after it starts");
+        }
+    }
 }
 
 mod unordered_set_json {
@@ -84,6 +270,7 @@ impl Contract {
         &mut self,
         series_name: String,
         bytes: U64,
+        src_hash: String,
         params: SeriesParams,
         account_id: ValidAccountId,
         royalty: Option<HashMap<AccountId, u32>>,
@@ -92,7 +279,7 @@ impl Contract {
         assert_at_least_one_yocto();
         let initial_storage_usage = env::storage_usage();
 
-        self.series_create_internal(series_name.clone(), bytes, params, royalty);
+        let series_name = self.series_create_internal(series_name, bytes, src_hash, params, royalty);
 
         let mut series = self
             .series_by_name
@@ -107,7 +294,10 @@ impl Contract {
 
         series.approved_account_ids.insert(account_id.as_ref());
 
-        let storage_cost = env::storage_byte_cost() * Balance::from(bytes.0 + env::storage_usage() - initial_storage_usage);
+        let deposit = self.internal_pay_storage(
+            env::attached_deposit(),
+            bytes.0 + env::storage_usage() - initial_storage_usage,
+        );
 
         if let Some(msg) = msg {
             ext_non_fungible_series_approval_receiver::series_on_approve(
@@ -115,11 +305,11 @@ impl Contract {
                 series.owner_id,
                 msg,
                 account_id.as_ref(),
-                env::attached_deposit()
-                    .checked_sub(storage_cost)
-                    .expect("Deposit not enough for series"),
+                deposit,
                 env::prepaid_gas() - GAS_FOR_SERIES_APPROVE,
             );
+        } else if deposit > 1 {
+            Promise::new(series.owner_id).transfer(deposit);
         }
     }
 
@@ -128,38 +318,54 @@ impl Contract {
         &mut self,
         series_name: String,
         bytes: U64,
+        src_hash: String,
         params: SeriesParams,
         royalty: Option<HashMap<AccountId, u32>>,
     ) {
         assert_at_least_one_yocto();
         let initial_storage_usage = env::storage_usage();
 
-        self.series_create_internal(series_name, bytes, params, royalty);
+        self.series_create_internal(series_name, bytes, src_hash, params, royalty);
 
-        refund_deposit(initial_storage_usage, bytes.0 + env::storage_usage(), None);
+        self.internal_refund_deposit(initial_storage_usage, bytes.0 + env::storage_usage(), None);
     }
 
+    /// returns the canonical series_name the series is stored under
     fn series_create_internal(
         &mut self,
         series_name: String,
         bytes: U64,
+        src_hash: String,
         params: SeriesParams,
         royalty: Option<HashMap<AccountId, u32>>,
-    ) {
+    ) -> SeriesName {
+        let series_name = canonical_series_name(&series_name);
+        self.assert_not_paused(Feature::SeriesCreation);
         let owner_id = env::predecessor_account_id();
-        let name = series_name.to_lowercase();
+        assert!(
+            src_hash.len() == 64 && src_hash.chars().all(|c| matches!(c, '0'..='9' | 'a'..='f')),
+            "src_hash must be a lowercase hex sha256"
+        );
+        params.assert_valid_schema();
+        assert_valid_phases(&params.phases);
+        let phases_minted = vec![U64(0); params.phases.len()];
 
         assert!(
             self.series_by_name
                 .insert(
-                    &name,
+                    &series_name,
                     &Series {
-                        series_name: name.clone(),
+                        series_name: series_name.clone(),
                         src: Src::Bytes(bytes),
+                        src_hash: Some(src_hash),
                         royalty: royalty.unwrap_or_default(),
                         owner_id: owner_id.clone(),
                         created_at: env::block_timestamp().into(),
                         params,
+                        phases_minted,
+                        closed: false,
+                        reserved_minted: U64(0),
+                        minted_count: U64(0),
                         approved_account_ids: UnorderedSet::new(StorageKey::SeriesApprovedIds {
                             series_name_hash: hash_account_id(&series_name),
                         })
@@ -176,6 +382,14 @@ impl Contract {
         });
         series_per_owner.insert(&series_name);
         self.series_per_owner.insert(&owner_id, &series_per_owner);
+
+        EventLogVariant::SeriesCreate(vec![SeriesCreateLog {
+            owner_id,
+            series_name: series_name.clone(),
+        }])
+        .emit();
+
+        series_name
     }
 
     #[payable]
@@ -185,6 +399,7 @@ impl Contract {
         account_id: ValidAccountId,
         msg: Option<String>,
     ) {
+        let series_name = normalize_series_name(&series_name);
         assert_at_least_one_yocto();
         let initial_storage_usage = env::storage_usage();
 
@@ -200,7 +415,10 @@ impl Contract {
 
         series.approved_account_ids.insert(account_id.as_ref());
 
-        let storage_used = env::storage_usage() - initial_storage_usage;
+        let deposit = self.internal_pay_storage(
+            env::attached_deposit(),
+            env::storage_usage() - initial_storage_usage,
+        );
 
         if let Some(msg) = msg {
             ext_non_fungible_series_approval_receiver::series_on_approve(
@@ -208,16 +426,17 @@ impl Contract {
                 series.owner_id,
                 msg,
                 account_id.as_ref(),
-                env::attached_deposit()
-                    .checked_sub(env::storage_byte_cost() * Balance::from(storage_used))
-                    .expect("Must pay enough to cover storage"),
+                deposit,
                 env::prepaid_gas() - GAS_FOR_SERIES_APPROVE,
             )
             .as_return(); // Returning this promise
+        } else if deposit > 1 {
+            Promise::new(series.owner_id).transfer(deposit);
         }
     }
 
     pub fn series_remove_approval(&mut self, series_name: String, account_id: ValidAccountId) {
+        let series_name = normalize_series_name(&series_name);
         let predecessor_account_id = env::predecessor_account_id();
         let initial_storage_usage = env::storage_usage();
 
@@ -231,7 +450,15 @@ impl Contract {
             "Must be series owner"
         );
 
-        series.approved_account_ids.remove(account_id.as_ref());
+        if series.approved_account_ids.remove(account_id.as_ref()) {
+            // series sales are listed under the series name
+            ext_non_fungible_approval_receiver::nft_on_revoke(
+                series_name,
+                account_id.as_ref(),
+                NO_DEPOSIT,
+                GAS_FOR_NFT_ON_REVOKE,
+            );
+        }
 
         let refund =
             env::storage_byte_cost() * (initial_storage_usage - env::storage_usage()) as u128;
@@ -241,6 +468,7 @@ impl Contract {
     }
 
     pub fn series_update(&mut self, series_name: String, src: String) {
+        let series_name = normalize_series_name(&series_name);
         let mut series = self
             .series_by_name
             .get(&series_name)
@@ -255,9 +483,382 @@ impl Contract {
         } else {
             env::panic(b"Cannot set src twice")
         }
+        if let Some(src_hash) = &series.src_hash {
+            assert_eq!(&hex_sha256(src.as_bytes()), src_hash, "Src does not match src_hash");
+        }
 
         series.src = Src::Code(src);
         self.series_by_name.insert(&series_name, &series);
+
+        EventLogVariant::SeriesUpdate(vec![SeriesUpdateLog {
+            owner_id: series.owner_id,
+            series_name,
+        }])
+        .emit();
+    }
+
+    /// appends a chunk of src for series too large to upload in a single series_update
+    pub fn series_src_append(&mut self, series_name: String, chunk: String) {
+        let series_name = normalize_series_name(&series_name);
+        let mut series = self
+            .series_by_name
+            .get(&series_name)
+            .unwrap_or_else(|| panic!("No series {}", series_name));
+        assert_eq!(
+            series.owner_id,
+            env::predecessor_account_id(),
+            "Must be series owner"
+        );
+
+        series.src = match series.src {
+            Src::Bytes(bytes) => Src::Partial { bytes, code: chunk },
+            Src::Partial { bytes, mut code } => {
+                code.push_str(&chunk);
+                Src::Partial { bytes, code }
+            }
+            Src::Code(_) => env::panic(b"Cannot set src twice"),
+        };
+        if let Src::Partial { bytes, code } = &series.src {
+            assert!(
+                code.len() as u64 <= bytes.0,
+                "Src exceeds declared bytes {}",
+                bytes.0
+            );
+        }
+        self.series_by_name.insert(&series_name, &series);
+    }
+
+    /// completes a chunked upload once exactly the declared bytes have been appended
+    pub fn series_src_finalize(&mut self, series_name: String) {
+        let series_name = normalize_series_name(&series_name);
+        let mut series = self
+            .series_by_name
+            .get(&series_name)
+            .unwrap_or_else(|| panic!("No series {}", series_name));
+        assert_eq!(
+            series.owner_id,
+            env::predecessor_account_id(),
+            "Must be series owner"
+        );
+
+        series.src = match series.src {
+            Src::Partial { bytes, code } => {
+                assert_eq!(bytes.0, code.len() as u64, "Must be exactly the same bytes");
+                if let Some(src_hash) = &series.src_hash {
+                    assert_eq!(&hex_sha256(code.as_bytes()), src_hash, "Src does not match src_hash");
+                }
+                Src::Code(code)
+            }
+            Src::Bytes(_) => env::panic(b"No src uploaded"),
+            Src::Code(_) => env::panic(b"Cannot set src twice"),
+        };
+        self.series_by_name.insert(&series_name, &series);
+
+        EventLogVariant::SeriesUpdate(vec![SeriesUpdateLog {
+            owner_id: series.owner_id,
+            series_name,
+        }])
+        .emit();
+    }
+
+    /// replaces the mint phases of a series, phases that have started must stay at their index
+    /// with the same starts_at and keep their minted counts
+    #[payable]
+    pub fn series_set_mint_phases(&mut self, series_name: String, phases: Vec<MintPhase>) {
+        let series_name = normalize_series_name(&series_name);
+        assert_at_least_one_yocto();
+        let initial_storage_usage = env::storage_usage();
+
+        let mut series = self
+            .series_by_name
+            .get(&series_name)
+            .unwrap_or_else(|| panic!("No series {}", series_name));
+        assert_eq!(
+            series.owner_id,
+            env::predecessor_account_id(),
+            "Must be series owner"
+        );
+        assert_valid_phases(&phases);
+
+        let now = env::block_timestamp();
+        let mut phases_minted = vec![U64(0); phases.len()];
+        for (i, phase) in series.params.phases.iter().enumerate() {
+            if phase.has_started(now) {
+                assert!(
+                    matches!(phases.get(i), Some(p) if p.starts_at == phase.starts_at),
+                    "Cannot remove or move mint phase {} after it started",
+                    i
+                );
+                phases_minted[i] = series.phases_minted[i];
+            }
+        }
+        series.phases_minted = phases_minted;
+        series.params.phases = phases;
+        self.series_by_name.insert(&series_name, &series);
+
+        self.internal_refund_deposit(initial_storage_usage, env::storage_usage(), None);
+    }
+
+    /// adds accounts to the allowlist of a mint phase, the series owner pays for storage
+    #[payable]
+    pub fn series_allowlist_add(
+        &mut self,
+        series_name: String,
+        phase_index: u32,
+        account_ids: Vec<ValidAccountId>,
+    ) {
+        let series_name = normalize_series_name(&series_name);
+        assert_at_least_one_yocto();
+        let initial_storage_usage = env::storage_usage();
+
+        let series = self
+            .series_by_name
+            .get(&series_name)
+            .unwrap_or_else(|| panic!("No series {}", series_name));
+        assert_eq!(
+            series.owner_id,
+            env::predecessor_account_id(),
+            "Must be series owner"
+        );
+        assert!(
+            (phase_index as usize) < series.params.phases.len(),
+            "No mint phase {}",
+            phase_index
+        );
+
+        let mut allowlists = self.series_allowlists(&series_name);
+        for account_id in account_ids {
+            allowlists.insert(&series.allowlist_key(phase_index, account_id.as_ref()));
+        }
+        self.mint_phase_allowlists.insert(&series_name, &allowlists);
+
+        self.internal_refund_deposit(initial_storage_usage, env::storage_usage(), None);
+    }
+
+    #[payable]
+    pub fn series_allowlist_remove(
+        &mut self,
+        series_name: String,
+        phase_index: u32,
+        account_ids: Vec<ValidAccountId>,
+    ) {
+        let series_name = normalize_series_name(&series_name);
+        assert_one_yocto();
+        let initial_storage_usage = env::storage_usage();
+
+        let series = self
+            .series_by_name
+            .get(&series_name)
+            .unwrap_or_else(|| panic!("No series {}", series_name));
+        assert_eq!(
+            series.owner_id,
+            env::predecessor_account_id(),
+            "Must be series owner"
+        );
+
+        let mut allowlists = self.series_allowlists(&series_name);
+        for account_id in account_ids {
+            allowlists.remove(&series.allowlist_key(phase_index, account_id.as_ref()));
+        }
+        if allowlists.is_empty() {
+            self.mint_phase_allowlists.remove(&series_name);
+        } else {
+            self.mint_phase_allowlists.insert(&series_name, &allowlists);
+        }
+
+        self.internal_refund_deposit(initial_storage_usage, env::storage_usage(), None);
+    }
+
+    #[payable]
+    pub fn series_set_mint_price(&mut self, series_name: String, mint_price: Option<U128>) {
+        let series_name = normalize_series_name(&series_name);
+        assert_one_yocto();
+        let mut series = self
+            .series_by_name
+            .get(&series_name)
+            .unwrap_or_else(|| panic!("No series {}", series_name));
+        assert_eq!(
+            series.owner_id,
+            env::predecessor_account_id(),
+            "Must be series owner"
+        );
+
+        series.params.mint_price = mint_price;
+        self.series_by_name.insert(&series_name, &series);
+    }
+
+    /// stops all further minting of the series, cannot be undone
+    #[payable]
+    pub fn series_close(&mut self, series_name: String) {
+        let series_name = normalize_series_name(&series_name);
+        assert_one_yocto();
+        let mut series = self
+            .series_by_name
+            .get(&series_name)
+            .unwrap_or_else(|| panic!("No series {}", series_name));
+        assert_eq!(
+            series.owner_id,
+            env::predecessor_account_id(),
+            "Must be series owner"
+        );
+        assert!(!series.closed, "Series is already closed");
+
+        series.closed = true;
+        self.series_by_name.insert(&series_name, &series);
+
+        EventLogVariant::SeriesUpdate(vec![SeriesUpdateLog {
+            owner_id: series.owner_id,
+            series_name,
+        }])
+        .emit();
+    }
+
+    #[payable]
+    pub fn series_reduce_max_supply(&mut self, series_name: String, max_supply: U64) {
+        let series_name = normalize_series_name(&series_name);
+        assert_one_yocto();
+        let mut series = self
+            .series_by_name
+            .get(&series_name)
+            .unwrap_or_else(|| panic!("No series {}", series_name));
+        assert_eq!(
+            series.owner_id,
+            env::predecessor_account_id(),
+            "Must be series owner"
+        );
+        assert!(
+            max_supply.0 < series.params.max_supply.0,
+            "Can only reduce max_supply"
+        );
+        let public_minted = series.public_minted();
+        assert!(
+            max_supply.0 >= public_minted,
+            "Cannot reduce max_supply below {} minted tokens",
+            public_minted
+        );
+
+        series.params.max_supply = max_supply;
+        self.series_by_name.insert(&series_name, &series);
+
+        EventLogVariant::SeriesUpdate(vec![SeriesUpdateLog {
+            owner_id: series.owner_id,
+            series_name,
+        }])
+        .emit();
+    }
+
+    /// hands the series to new_owner_id, optionally revoking approvals and moving the
+    /// old owner's royalty share to the new owner
+    #[payable]
+    pub fn series_transfer(
+        &mut self,
+        series_name: String,
+        new_owner_id: ValidAccountId,
+        clear_approvals: Option<bool>,
+        transfer_royalty: Option<bool>,
+    ) {
+        let series_name = normalize_series_name(&series_name);
+        assert_at_least_one_yocto();
+        let initial_storage_usage = env::storage_usage();
+        let old_owner_id = env::predecessor_account_id();
+        let new_owner_id: AccountId = new_owner_id.into();
+
+        let mut series = self
+            .series_by_name
+            .get(&series_name)
+            .unwrap_or_else(|| panic!("No series {}", series_name));
+        assert_eq!(series.owner_id, old_owner_id, "Must be series owner");
+        assert_ne!(old_owner_id, new_owner_id, "Series is already owned by new_owner_id");
+
+        if clear_approvals.unwrap_or(false) {
+            // series sales are listed under the series name
+            notify_revoked(&series_name, series.approved_account_ids.iter());
+            series.approved_account_ids.clear();
+        }
+        if transfer_royalty.unwrap_or(false) {
+            if let Some(amount) = series.royalty.remove(&old_owner_id) {
+                *series.royalty.entry(new_owner_id.clone()).or_insert(0) += amount;
+            }
+        }
+        series.owner_id = new_owner_id.clone();
+        self.series_by_name.insert(&series_name, &series);
+
+        let mut series_per_owner = self
+            .series_per_owner
+            .get(&old_owner_id)
+            .expect("Should be series per owner");
+        series_per_owner.remove(&series_name);
+        if series_per_owner.is_empty() {
+            self.series_per_owner.remove(&old_owner_id);
+        } else {
+            self.series_per_owner.insert(&old_owner_id, &series_per_owner);
+        }
+        let mut series_per_owner = self.series_per_owner.get(&new_owner_id).unwrap_or_else(|| {
+            UnorderedSet::new(StorageKey::SeriesPerOwnerInner {
+                account_id_hash: hash_account_id(&new_owner_id),
+            })
+        });
+        series_per_owner.insert(&series_name);
+        self.series_per_owner.insert(&new_owner_id, &series_per_owner);
+
+        EventLogVariant::SeriesTransfer(vec![SeriesTransferLog {
+            old_owner_id,
+            new_owner_id,
+            series_name,
+        }])
+        .emit();
+
+        self.internal_refund_deposit(initial_storage_usage, env::storage_usage(), None);
+    }
+
+    /// removes a series with no tokens and refunds its storage, including unused src bytes
+    #[payable]
+    pub fn series_delete(&mut self, series_name: String) {
+        let series_name = normalize_series_name(&series_name);
+        assert_one_yocto();
+        self.assert_records_migrated();
+        let initial_storage_usage = env::storage_usage();
+        let owner_id = env::predecessor_account_id();
+
+        let mut series = self
+            .series_by_name
+            .get(&series_name)
+            .unwrap_or_else(|| panic!("No series {}", series_name));
+        assert_eq!(series.owner_id, owner_id, "Must be series owner");
+        // even burned tokens keep their ids, so a recreated series must not reuse them
+        assert_eq!(series.minted_count.0, 0, "Cannot delete a series with minted tokens");
+
+        let unused_src_bytes = match &series.src {
+            Src::Bytes(bytes) => bytes.0,
+            Src::Partial { bytes, code } => bytes.0 - code.len() as u64,
+            Src::Code(_) => 0,
+        };
+
+        // series sales are listed under the series name
+        notify_revoked(&series_name, series.approved_account_ids.iter());
+        series.approved_account_ids.clear();
+        self.series_by_name.remove(&series_name);
+        // allowlist storage was paid by the owner, so it is refunded with the rest
+        if let Some(mut allowlists) = self.mint_phase_allowlists.remove(&series_name) {
+            allowlists.clear();
+        }
+
+        let mut series_per_owner = self
+            .series_per_owner
+            .get(&owner_id)
+            .expect("Should be series per owner");
+        series_per_owner.remove(&series_name);
+        if series_per_owner.is_empty() {
+            self.series_per_owner.remove(&owner_id);
+        } else {
+            self.series_per_owner.insert(&owner_id, &series_per_owner);
+        }
+
+        let refund = env::storage_byte_cost()
+            * Balance::from(initial_storage_usage - env::storage_usage() + unused_src_bytes);
+        if refund > 1 {
+            Promise::new(owner_id).transfer(refund);
+        }
     }
 
     /// views
@@ -266,25 +867,86 @@ impl Contract {
         U64(self.series_by_name.keys_as_vector().len())
     }
 
-    pub fn series_data(&self, series_name: SeriesName) -> Series {
-        self.series_by_name
+    pub fn series_data(&self, series_name: SeriesName) -> JsonSeries {
+        let series_name = normalize_series_name(&series_name);
+        self.series_json(
+            self.series_by_name
+                .get(&series_name)
+                .unwrap_or_else(|| panic!("No series {}", series_name)),
+        )
+    }
+
+    pub fn series_mint_phase(&self, series_name: SeriesName) -> Option<ActiveMintPhase> {
+        let series_name = normalize_series_name(&series_name);
+        let series = self
+            .series_by_name
+            .get(&series_name)
+            .unwrap_or_else(|| panic!("No series {}", series_name));
+        let index = series.active_phase_index()?;
+        let phase = series.params.phases[index].clone();
+        let minted = series.phases_minted[index];
+        let mut remaining = series.params.max_supply.0.saturating_sub(series.public_minted());
+        if let Some(max_supply) = phase.max_supply {
+            remaining = min(remaining, max_supply.0.saturating_sub(minted.0));
+        }
+        Some(ActiveMintPhase {
+            index: index as u32,
+            phase,
+            minted,
+            remaining: U64(remaining),
+        })
+    }
+
+    pub fn series_allowlist_contains(
+        &self,
+        series_name: SeriesName,
+        phase_index: u32,
+        account_id: ValidAccountId,
+    ) -> bool {
+        let series_name = normalize_series_name(&series_name);
+        let series = self
+            .series_by_name
+            .get(&series_name)
+            .unwrap_or_else(|| panic!("No series {}", series_name));
+        self.series_allowlists(&series_name)
+            .contains(&series.allowlist_key(phase_index, account_id.as_ref()))
+    }
+
+    /// whether a token with these args could be minted, or an owner could switch to them,
+    /// empty owner args are the series defaults and never taken
+    pub fn series_args_available(
+        &self,
+        series_name: SeriesName,
+        mint: Vec<String>,
+        owner: Vec<String>,
+    ) -> SeriesArgsAvailable {
+        let series_name = normalize_series_name(&series_name);
+        let series = self
+            .series_by_name
             .get(&series_name)
-            .unwrap_or_else(|| panic!("No series {}", series_name))
+            .unwrap_or_else(|| panic!("No series {}", series_name));
+        SeriesArgsAvailable {
+            mint: !series.params.enforce_unique_mint_args
+                || !self.series_mint_arg_hashes.contains(&hash_series_args(&series_name, &mint)),
+            owner: !series.params.enforce_unique_owner_args
+                || owner.is_empty()
+                || !self.series_owner_arg_hashes.contains(&hash_series_args(&series_name, &owner)),
+        }
     }
 
-    pub fn series_range(&self, from_index: U64, limit: u64) -> Vec<Series> {
+    pub fn series_range(&self, from_index: U64, limit: u64) -> Vec<JsonSeries> {
         let keys = self.series_by_name.keys_as_vector();
         let start = u64::from(from_index);
         let end = min(start + limit, keys.len());
         (start..end)
-            .map(|i| self.series_by_name.get(&keys.get(i).unwrap()).unwrap())
+            .map(|i| self.series_json(self.series_by_name.get(&keys.get(i).unwrap()).unwrap()))
             .collect()
     }
 
-    pub fn series_batch(&self, series_names: Vec<String>) -> Vec<Series> {
+    pub fn series_batch(&self, series_names: Vec<String>) -> Vec<JsonSeries> {
         series_names
             .into_iter()
-            .map(|series_name| self.series_by_name.get(&series_name).unwrap())
+            .map(|series_name| self.series_json(self.series_by_name.get(&normalize_series_name(&series_name)).unwrap()))
             .collect()
     }
     
@@ -305,7 +967,7 @@ impl Contract {
         account_id: AccountId,
         from_index: U64,
         limit: u64,
-    ) -> Vec<Series> {
+    ) -> Vec<JsonSeries> {
         let series_per_owner = self.series_per_owner.get(&account_id);
         let series = if let Some(series_per_owner) = series_per_owner {
             series_per_owner
@@ -316,9 +978,29 @@ impl Contract {
         let start = u64::from(from_index);
         let end = min(start + limit, keys.len());
         (start..end)
-            .map(|i| self.series_by_name.get(&keys.get(i).unwrap()).unwrap())
+            .map(|i| self.series_json(self.series_by_name.get(&keys.get(i).unwrap()).unwrap()))
             .collect()
     }
+
+    /// allowlist keys of every mint phase of a series, see Series::allowlist_key
+    pub(crate) fn series_allowlists(&self, series_name: &SeriesName) -> UnorderedSet<CryptoHash> {
+        self.mint_phase_allowlists.get(series_name).unwrap_or_else(|| {
+            UnorderedSet::new(StorageKey::MintPhaseAllowlistsInner {
+                series_name_hash: hash_account_id(series_name),
+            })
+        })
+    }
+
+    fn series_json(&self, series: Series) -> JsonSeries {
+        let current_supply = self
+            .tokens_per_series
+            .get(&series.series_name)
+            .map_or(0, |tokens| tokens.len());
+        JsonSeries {
+            series,
+            current_supply: U64(current_supply),
+        }
+    }
 }
 
 #[ext_contract(ext_non_fungible_series_approval_receiver)]
@@ -0,0 +1,151 @@
+use crate::*;
+
+/// storage_deposits entry: key prefix + borsh account_id (max 64 chars) + balance + record overhead
+const STORAGE_BALANCE_BYTES: StorageUsage = 1 + 4 + 64 + 16 + 40;
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct StorageBalance {
+    pub total: U128,
+    pub available: U128,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct StorageBalanceBounds {
+    pub min: U128,
+    pub max: Option<U128>,
+}
+
+pub(crate) fn storage_balance_min() -> Balance {
+    env::storage_byte_cost() * Balance::from(STORAGE_BALANCE_BYTES)
+}
+
+fn storage_balance(total: Balance) -> StorageBalance {
+    StorageBalance {
+        total: U128(total),
+        available: U128(total.saturating_sub(storage_balance_min())),
+    }
+}
+
+pub trait StorageManagement {
+    fn storage_deposit(
+        &mut self,
+        account_id: Option<ValidAccountId>,
+        registration_only: Option<bool>,
+    ) -> StorageBalance;
+
+    fn storage_withdraw(&mut self, amount: Option<U128>) -> StorageBalance;
+
+    fn storage_unregister(&mut self, force: Option<bool>) -> bool;
+
+    fn storage_balance_bounds(&self) -> StorageBalanceBounds;
+
+    fn storage_balance_of(&self, account_id: ValidAccountId) -> Option<StorageBalance>;
+}
+
+#[near_bindgen]
+impl StorageManagement for Contract {
+    #[payable]
+    fn storage_deposit(
+        &mut self,
+        account_id: Option<ValidAccountId>,
+        registration_only: Option<bool>,
+    ) -> StorageBalance {
+        let amount = env::attached_deposit();
+        let account_id = account_id
+            .map(|a| a.into())
+            .unwrap_or_else(env::predecessor_account_id);
+        let registration_only = registration_only.unwrap_or(false);
+
+        let balance = if let Some(balance) = self.storage_deposits.get(&account_id) {
+            if registration_only {
+                if amount > 0 {
+                    Promise::new(env::predecessor_account_id()).transfer(amount);
+                }
+                balance
+            } else {
+                balance + amount
+            }
+        } else {
+            let min_balance = storage_balance_min();
+            assert!(
+                amount >= min_balance,
+                "Requires minimum deposit of {}",
+                min_balance
+            );
+            if registration_only {
+                let refund = amount - min_balance;
+                if refund > 0 {
+                    Promise::new(env::predecessor_account_id()).transfer(refund);
+                }
+                min_balance
+            } else {
+                amount
+            }
+        };
+        self.storage_deposits.insert(&account_id, &balance);
+
+        storage_balance(balance)
+    }
+
+    #[payable]
+    fn storage_withdraw(&mut self, amount: Option<U128>) -> StorageBalance {
+        assert_one_yocto();
+        let account_id = env::predecessor_account_id();
+        let balance = self
+            .storage_deposits
+            .get(&account_id)
+            .expect("Account is not registered");
+        let available = balance - storage_balance_min();
+        let amount = amount.map(|a| a.0).unwrap_or(available);
+        assert!(
+            amount <= available,
+            "Cannot withdraw more than the available balance of {}",
+            available
+        );
+
+        let balance = balance - amount;
+        self.storage_deposits.insert(&account_id, &balance);
+        if amount > 0 {
+            Promise::new(account_id).transfer(amount);
+        }
+
+        storage_balance(balance)
+    }
+
+    /// prepaid balances are not tied to any stored data, so `force` is never required
+    #[payable]
+    fn storage_unregister(&mut self, force: Option<bool>) -> bool {
+        assert_one_yocto();
+        let _ = force;
+        let account_id = env::predecessor_account_id();
+        if let Some(balance) = self.storage_deposits.remove(&account_id) {
+            Promise::new(account_id).transfer(balance);
+            true
+        } else {
+            false
+        }
+    }
+
+    fn storage_balance_bounds(&self) -> StorageBalanceBounds {
+        StorageBalanceBounds {
+            min: U128(storage_balance_min()),
+            max: None,
+        }
+    }
+
+    fn storage_balance_of(&self, account_id: ValidAccountId) -> Option<StorageBalance> {
+        self.storage_deposits
+            .get(account_id.as_ref())
+            .map(storage_balance)
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// CUSTOM - view cost of adding account_id to a token's approvals
+    pub fn storage_cost_for_approval(&self, account_id: ValidAccountId) -> U128 {
+        U128(env::storage_byte_cost() * Balance::from(bytes_for_approved_account_id(account_id.as_ref())))
+    }
+}
@@ -1,7 +1,6 @@
 use crate::*;
 
 pub type TokenId = String;
-pub type Payout = HashMap<AccountId, U128>;
 
 #[derive(BorshDeserialize, BorshSerialize)]
 pub struct Token {
@@ -18,6 +17,10 @@ pub struct TokenData {
     // CUSTOM
     pub series_args: SeriesArgs,
     pub num_transfers: U64,
+    /// hex sha256 of the mint block's random seed and token id, None for tokens minted before seeds
+    pub seed: Option<String>,
+    pub num_owner_args_updates: u32,
+    pub owner_args_updated_at: Option<U64>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -33,24 +36,47 @@ pub struct JsonToken {
     // CUSTOM
     pub series_args: SeriesArgs,
     pub num_transfers: U64,
+    pub seed: Option<String>,
+    pub num_owner_args_updates: u32,
+}
+
+/// oldest entries are dropped once a token has this many
+pub const MAX_OWNER_ARGS_HISTORY: usize = 20;
+
+/// owner args a token had before an update_token_owner_args call
+#[derive(BorshDeserialize, BorshSerialize, Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct OwnerArgsChange {
+    pub updated_at: U64,
+    pub account_id: AccountId,
+    pub owner_args: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, BorshDeserialize, BorshSerialize)]
 #[serde(crate = "near_sdk::serde")]
 pub struct TokenMetadata {
-    pub media: Option<String>,
-    pub issued_at: Option<String>,
+    pub title: Option<String>, // ex. "Arch Nemesis: Mail Carrier" or "Parcel #5055"
+    pub description: Option<String>, // free-form description
+    pub media: Option<String>, // URL to associated media, preferably to decentralized, content-addressed storage
+    pub media_hash: Option<Base64VecU8>, // Base64-encoded sha256 hash of content referenced by the `media` field, not set by this contract
+    pub copies: Option<U64>, // number of copies of this set of metadata in existence when token was minted.
+    pub issued_at: Option<String>, // When token was minted, block timestamp in nanoseconds
+    pub expires_at: Option<String>, // When token expires, block timestamp in nanoseconds
+    pub starts_at: Option<String>, // When token starts being valid, block timestamp in nanoseconds
+    pub updated_at: Option<String>, // When owner args were last updated, block timestamp in nanoseconds
+    pub extra: Option<String>, // anything extra the NFT wants to store on-chain. Can be stringified JSON.
+    pub reference: Option<String>, // URL to an off-chain JSON file with more info.
+    pub reference_hash: Option<Base64VecU8>, // Base64-encoded sha256 hash of JSON from reference field, not set by this contract
 }
 
-
 #[near_bindgen]
 impl Contract {
-
     /// custom token methods
 
     #[payable]
     pub fn update_token_owner_args(&mut self, token_id: TokenId, owner_args: Vec<String>) {
         assert_at_least_one_yocto();
+        self.assert_records_migrated();
         let predecessor_account_id = env::predecessor_account_id();
         let initial_storage_usage = env::storage_usage();
 
@@ -59,7 +85,10 @@ impl Contract {
             .get(&token_id)
             .unwrap_or_else(|| panic!("No token {}", token_id));
 
-        assert_eq!(token.owner_id, predecessor_account_id, "Must be token owner");
+        assert_eq!(
+            token.owner_id, predecessor_account_id,
+            "Must be token owner"
+        );
 
         let mut token_data = self
             .token_data_by_id
@@ -71,25 +100,210 @@ impl Contract {
             .get(&token_data.series_args.series_name)
             .unwrap_or_else(|| panic!("No series {}", token_data.series_args.series_name));
 
-        assert_eq!(series.params.owner.len(), owner_args.len(), "Incorrect length of owner_args for series");
-        
+        assert_eq!(
+            series.params.owner.len(),
+            owner_args.len(),
+            "Incorrect length of owner_args for series"
+        );
+        series.params.assert_valid_owner_args(&owner_args);
+
+        // CUSTOM - series defined update limits
+        if let Some(max_updates) = series.params.max_owner_args_updates {
+            assert!(
+                token_data.num_owner_args_updates < max_updates,
+                "Token has reached the max of {} owner args updates",
+                max_updates
+            );
+        }
+        if let (Some(cooldown), Some(updated_at)) = (
+            series.params.owner_args_update_cooldown,
+            token_data.owner_args_updated_at,
+        ) {
+            let next_update_at = updated_at.0.saturating_add(cooldown.0);
+            assert!(
+                env::block_timestamp() >= next_update_at,
+                "Owner args cannot be updated again until {}",
+                next_update_at
+            );
+        }
+        let fee = series.params.owner_args_update_fee.map_or(0, |fee| fee.0);
+        assert!(
+            env::attached_deposit() >= fee,
+            "Must attach at least the update fee of {}",
+            fee
+        );
+
         if series.params.enforce_unique_owner_args {
-            let previous_owner_arg_hash = hash_account_id(&format!("{}{}", series.series_name, token_data.series_args.owner.join(ARGS_DELIMETER)));
-            self.series_owner_arg_hashes.remove(&previous_owner_arg_hash);
-            let owner_arg_hash = hash_account_id(&format!("{}{}", series.series_name, owner_args.join(ARGS_DELIMETER)));
+            self.series_owner_arg_hashes.remove(&hash_series_args(
+                &series.series_name,
+                &token_data.series_args.owner,
+            ));
             assert!(
-                self.series_owner_arg_hashes.insert(&owner_arg_hash),
+                self.series_owner_arg_hashes
+                    .insert(&hash_series_args(&series.series_name, &owner_args)),
                 "Token in series has identical owner args"
             );
         }
 
-        token_data.series_args.owner = owner_args;
+        let mut history = self
+            .owner_args_history_by_id
+            .get(&token_id)
+            .unwrap_or_default();
+        if history.len() >= MAX_OWNER_ARGS_HISTORY {
+            history.remove(0);
+        }
+        history.push(OwnerArgsChange {
+            updated_at: env::block_timestamp().into(),
+            account_id: predecessor_account_id.clone(),
+            owner_args: std::mem::replace(&mut token_data.series_args.owner, owner_args.clone()),
+        });
+        self.owner_args_history_by_id.insert(&token_id, &history);
+        token_data.num_owner_args_updates += 1;
+        token_data.owner_args_updated_at = Some(env::block_timestamp().into());
+        token_data.metadata.updated_at = Some(env::block_timestamp().to_string());
         self.token_data_by_id.insert(&token_id, &token_data);
 
+        EventLogVariant::UpdateTokenOwnerArgs(vec![UpdateTokenOwnerArgsLog {
+            owner_id: predecessor_account_id.clone(),
+            token_id,
+            owner_args,
+        }])
+        .emit();
+
         // TODO clean up
 
-        refund_deposit(initial_storage_usage, env::storage_usage(), None);
+        let refund = self.internal_settle_storage(
+            env::attached_deposit() - fee,
+            initial_storage_usage,
+            env::storage_usage(),
+        );
+        if refund > 1 {
+            Promise::new(predecessor_account_id).transfer(refund);
+        }
+
+        // the fee is split over the series royalty only, the contract royalty applies to sales
+        if fee > 0 {
+            let mut remainder = fee;
+            for (receiver_id, share) in series.royalty {
+                let amount = min(royalty_to_payout(share, fee).0, remainder);
+                if amount > 0 && receiver_id != series.owner_id {
+                    remainder -= amount;
+                    Promise::new(receiver_id).transfer(amount);
+                }
+            }
+            if remainder > 0 {
+                Promise::new(series.owner_id).transfer(remainder);
+            }
+        }
     }
 
-}
+    #[payable]
+    pub fn nft_batch_transfer(
+        &mut self,
+        receiver_id: ValidAccountId,
+        token_ids: Vec<TokenId>,
+        memo: Option<String>,
+    ) {
+        let receiver_id: AccountId = receiver_id.into();
+        self.internal_batch_transfer(
+            token_ids
+                .into_iter()
+                .map(|token_id| (token_id, receiver_id.clone()))
+                .collect(),
+            memo,
+        );
+    }
+
+    #[payable]
+    pub fn nft_batch_transfer_to(
+        &mut self,
+        transfers: Vec<(TokenId, ValidAccountId)>,
+        memo: Option<String>,
+    ) {
+        self.internal_batch_transfer(
+            transfers
+                .into_iter()
+                .map(|(token_id, receiver_id)| (token_id, receiver_id.into()))
+                .collect(),
+            memo,
+        );
+    }
+
+    fn internal_batch_transfer(
+        &mut self,
+        transfers: Vec<(TokenId, AccountId)>,
+        memo: Option<String>,
+    ) {
+        assert_one_yocto();
+        assert!(!transfers.is_empty(), "Must transfer at least one token");
+        let sender_id = env::predecessor_account_id();
+
+        // one refund per previous owner for all approvals cleared by the batch
+        let mut approved_account_ids_per_owner: HashMap<AccountId, Vec<AccountId>> = HashMap::new();
+        for (token_id, receiver_id) in transfers {
+            let previous_token =
+                self.internal_transfer(&sender_id, &receiver_id, &token_id, None, memo.clone());
+            approved_account_ids_per_owner
+                .entry(previous_token.owner_id)
+                .or_default()
+                .extend(previous_token.approved_account_ids.keys().cloned());
+        }
+
+        for (owner_id, approved_account_ids) in approved_account_ids_per_owner {
+            if !approved_account_ids.is_empty() {
+                refund_approved_account_ids_iter(owner_id, approved_account_ids.iter());
+            }
+        }
+    }
 
+    #[payable]
+    pub fn nft_burn(&mut self, token_id: TokenId) {
+        assert_one_yocto();
+        self.assert_records_migrated();
+        let predecessor_account_id = env::predecessor_account_id();
+        let initial_storage_usage = env::storage_usage();
+
+        let token = self
+            .tokens_by_id
+            .get(&token_id)
+            .unwrap_or_else(|| panic!("No token {}", token_id));
+
+        assert_eq!(
+            token.owner_id, predecessor_account_id,
+            "Must be token owner"
+        );
+
+        self.internal_remove_token(&token.owner_id, &token_id);
+
+        EventLogVariant::NftBurn(vec![NftBurnLog {
+            authorized_id: None,
+            owner_id: token.owner_id,
+            token_ids: vec![token_id],
+            memo: None,
+        }])
+        .emit();
+
+        // approvals are stored with the token so their storage is included in the refund
+        let refund =
+            env::storage_byte_cost() * Balance::from(initial_storage_usage - env::storage_usage());
+        if refund > 1 {
+            Promise::new(predecessor_account_id).transfer(refund);
+        }
+    }
+
+    /// views
+    pub fn token_owner_args_history(
+        &self,
+        token_id: TokenId,
+        from_index: U64,
+        limit: u64,
+    ) -> Vec<OwnerArgsChange> {
+        self.owner_args_history_by_id
+            .get(&token_id)
+            .unwrap_or_default()
+            .into_iter()
+            .skip(from_index.0 as usize)
+            .take(limit as usize)
+            .collect()
+    }
+}
@@ -11,6 +11,16 @@ import { Editor, examples } from './Editor';
 
 const PENDING_SERIES_UPDATE = '__PENDING_SERIES_UPDATE__';
 
+/// maps editor param types to the contract's validated param schema
+const toParamType = ({ type, min, max }) => {
+	switch (type) {
+	case 'int': return { type: 'int', min, max };
+	case 'float': case 'webgl-float': return { type: 'float', min, max };
+	case 'color-hex': return { type: 'color' };
+	default: return { type: 'any' };
+	}
+};
+
 export const Create = ({ app, views, update, dispatch, account }) => {
 
 	const { createMenu, consoleLog } = app;
@@ -90,9 +100,10 @@ export const Create = ({ app, views, update, dispatch, account }) => {
 		if (!result) return;
 		let [series_name, price] = result;
 		series_name = series_name.trim().toLowerCase();
-		if (!series_name.length || series_name.length > 255 || /[\s|^%#*@`+=?:;'"\{\}\[\]<>\/\\]/g.test(series_name)) {
+		// must match canonical_series_name in the contract
+		if (!/^[a-z0-9_-]{1,64}$/.test(series_name)) {
 			return dispatch(setDialog({
-				msg: 'Invalid Series Name. No special characters like: |^%#*@`+=?:;\'". Only "-" and "_".',
+				msg: 'Invalid Series Name. Up to 64 letters, numbers, "-" and "_".',
 				info: true
 			}));
 		}
@@ -113,6 +124,8 @@ export const Create = ({ app, views, update, dispatch, account }) => {
 			enforce_unique_owner_args: params.enforce_unique_owner_args || false,
 			mint: Object.keys(params.mint),
 			owner: Object.keys(params.owner),
+			mint_types: Object.values(params.mint).map(toParamType),
+			owner_types: Object.values(params.owner).map(toParamType),
 			packages: params.packages,
 		};
 
@@ -120,6 +133,7 @@ export const Create = ({ app, views, update, dispatch, account }) => {
 			account.functionCall(contractId, 'series_create_and_approve', {
 				series_name,
 				bytes: code.length.toString(),
+				src_hash: sha256(code),
 				params,
 				account_id: marketId,
 				msg: JSON.stringify({
@@ -132,6 +146,7 @@ export const Create = ({ app, views, update, dispatch, account }) => {
 			account.functionCall(contractId, 'series_create', {
 				series_name,
 				bytes: code.length.toString(),
+				src_hash: sha256(code),
 				params,
 			}, GAS, parseNearAmount('1'));
 		}
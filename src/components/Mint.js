@@ -3,7 +3,7 @@ import BN from 'bn.js';
 import { GAS, contractId, marketId, parseNearAmount } from '../state/near';
 import { get, set, del, ab2str, str2ab } from '../utils/storage';
 import { loadCodeFromSrc, getParams } from '../state/code';
-import { loadMint, getTokensForSeries } from '../state/views';
+import { loadMint } from '../state/views';
 import { setDialog, getFrameMedia, uploadMedia, getMediaUrl } from '../state/app';
 import { Menu } from './Menu';
 import { Params } from './Params';
@@ -79,19 +79,20 @@ export const Mint = ({ app, path, views, update, dispatch, account, wallet }) =>
 				throw 'Not for sale';
 			}
 			const mint = Object.values(args);
-			const owner = Object.values(getParams(series.src).params.owner).map((p) => JSON.stringify(p.default));
+			/// unique owner args are left empty so every new token starts with the defaults
+			const owner = series.params.enforce_unique_owner_args ? [] :
+				Object.values(getParams(series.src).params.owner).map((p) => JSON.stringify(p.default));
 			if (series.params.mint.length && !mint.length) {
 				throw 'Choose some values to make this unique';
 			}
-			if (series.params.enforce_unique_mint_args) {
-				const tokens = await dispatch(getTokensForSeries(series.series_name));
-				const exists = tokens.some(({ series_args }) => {
-					return series_args.mint.length &&
-						(JSON.stringify(series_args.mint) === JSON.stringify(mint));
-				});
-				if (exists) {
-					throw 'A token with these values exists, try another combination';
-				}
+			/// check before the market takes the offer, a failed lazy mint only refunds afterwards
+			const available = await account.viewFunction(contractId, 'series_args_available', {
+				series_name: series.series_name,
+				mint,
+				owner,
+			});
+			if (!available.mint) {
+				throw 'A token with these values exists, try another combination';
 			}
 
 			account.functionCall(marketId, 'offer', {
@@ -50,6 +50,29 @@ const getTokenAndSrc = async (token_id) => {
 	return { token, src };
 };
 
+/// creates an empty series to upload src to later, params default to 10 tokens without args
+const createSeries = (account, series_name, src, params, royalty) => account.functionCall({
+	contractId,
+	methodName: 'series_create',
+	args: {
+		series_name,
+		bytes: src.length.toString(),
+		src_hash: sha256(src),
+		params: {
+			max_supply: '10',
+			enforce_unique_mint_args: false,
+			enforce_unique_owner_args: false,
+			mint: [],
+			owner: [],
+			packages: [],
+			...params,
+		},
+		royalty,
+	},
+	gas: GAS,
+	attachedDeposit: parseNearAmount('0.5')
+});
+
 describe('deploy contract ' + contractName, () => {
 	let lazyArgs,
 		alice, aliceId, bob, bobId,
@@ -67,6 +90,7 @@ describe('deploy contract ' + contractName, () => {
 		example.royalty = {
 			'si1.testnet': 1000
 		};
+		example.src_hash = sha256(example.src);
 		const src = example.src;
 		example.src = undefined;
 		return { series_name, src };
@@ -427,4 +451,1105 @@ describe('deploy contract ' + contractName, () => {
 		expect(tokens.length).toEqual(1);
 	});
 
+	test('nft_payout splits balance exactly between royalties and token owner', async () => {
+		const [token] = await alice.viewFunction(contractId, 'nft_tokens_for_owner', {
+			account_id: alice.accountId,
+			from_index: '0',
+			limit: '1'
+		});
+
+		const { payout } = await alice.viewFunction(contractId, 'nft_payout', {
+			token_id: token.token_id,
+			balance: parseNearAmount('1'),
+			max_len_payout: 10,
+		});
+
+		expect(Object.keys(payout).includes('si1.testnet')).toEqual(true);
+		expect(Object.keys(payout).includes(alice.accountId)).toEqual(true);
+		const total = Object.values(payout).reduce((a, c) => a.add(new BN(c)), new BN('0'));
+		expect(total.toString()).toEqual(parseNearAmount('1'));
+	});
+
+	test('nft_payout gives the rounding dust of an odd balance to the token owner', async () => {
+		const [token] = await alice.viewFunction(contractId, 'nft_tokens_for_owner', {
+			account_id: alice.accountId,
+			from_index: '0',
+			limit: '1'
+		});
+
+		/// 10% of 9999 yocto is 999.9, royalties round down
+		const balance = '9999';
+		const { payout } = await alice.viewFunction(contractId, 'nft_payout', {
+			token_id: token.token_id,
+			balance,
+			max_len_payout: 10,
+		});
+
+		expect(payout['si1.testnet']).toEqual('999');
+		const royalties = Object.entries(payout)
+			.filter(([account_id]) => account_id !== alice.accountId)
+			.reduce((a, [, c]) => a.add(new BN(c)), new BN('0'));
+		expect(payout[alice.accountId]).toEqual(new BN(balance).sub(royalties).toString());
+		const total = Object.values(payout).reduce((a, c) => a.add(new BN(c)), new BN('0'));
+		expect(total.toString()).toEqual(balance);
+	});
+
+	test('alice burns lazy minted NFT', async () => {
+		const [token] = await alice.viewFunction(contractId, 'nft_tokens_for_owner', {
+			account_id: alice.accountId,
+			from_index: '0',
+			limit: '1'
+		});
+
+		await alice.functionCall({
+			contractId,
+			methodName: 'nft_burn',
+			args: {
+				token_id: token.token_id
+			},
+			gas: GAS,
+			attachedDeposit: '1'
+		});
+
+		const burned = await alice.viewFunction(contractId, 'nft_token', { token_id: token.token_id });
+		expect(burned).toEqual(null);
+		const supply = await alice.viewFunction(contractId, 'nft_supply_for_owner', { account_id: alice.accountId });
+		expect(supply).toEqual('0');
+	});
+
+	test('series enforces unique args at mint and reports availability', async () => {
+		const series_name = 'unique-args-' + t;
+		const src = '@params { mint: { speed: { default: 1 } }, owner: { color: { default: 1 } } } @params';
+		await createSeries(bob, series_name, src, {
+			enforce_unique_mint_args: true,
+			enforce_unique_owner_args: true,
+			mint: ['speed'],
+			owner: ['color'],
+			mint_price: parseNearAmount('0.1'),
+		});
+
+		/// owner args are left empty, so both tokens start with the series defaults
+		for (const speed of ['1', '2']) {
+			await alice.functionCall({
+				contractId,
+				methodName: 'series_mint',
+				args: {
+					series_mint_args: { series_name, mint: [speed], owner: [] }
+				},
+				gas: GAS,
+				attachedDeposit: parseNearAmount('0.2')
+			});
+		}
+
+		const available = await alice.viewFunction(contractId, 'series_args_available', {
+			series_name,
+			mint: ['1'],
+			owner: [],
+		});
+		expect(available).toEqual({ mint: false, owner: true });
+
+		try {
+			await alice.functionCall({
+				contractId,
+				methodName: 'series_mint',
+				args: {
+					series_mint_args: { series_name, mint: ['1'], owner: [] }
+				},
+				gas: GAS,
+				attachedDeposit: parseNearAmount('0.2')
+			});
+			expect(false).toEqual(true);
+		} catch (e) {
+			expect(/identical args/gi.test(e.toString())).toEqual(true);
+		}
+	});
+
+	test('owner args stay unique across tokens of a series', async () => {
+		const series_name = 'unique-args-' + t;
+		await alice.functionCall({
+			contractId,
+			methodName: 'update_token_owner_args',
+			args: {
+				token_id: series_name + SERIES_VARIANT_DELIMETER + 0,
+				owner_args: ['2']
+			},
+			gas: GAS,
+			attachedDeposit: parseNearAmount('0.1')
+		});
+		const available = await alice.viewFunction(contractId, 'series_args_available', {
+			series_name,
+			mint: ['3'],
+			owner: ['2'],
+		});
+		expect(available).toEqual({ mint: true, owner: false });
+
+		try {
+			await alice.functionCall({
+				contractId,
+				methodName: 'update_token_owner_args',
+				args: {
+					token_id: series_name + SERIES_VARIANT_DELIMETER + 1,
+					owner_args: ['2']
+				},
+				gas: GAS,
+				attachedDeposit: parseNearAmount('0.1')
+			});
+			expect(false).toEqual(true);
+		} catch (e) {
+			expect(/identical owner args/gi.test(e.toString())).toEqual(true);
+		}
+	});
+
+	test('series owner airdrops reserved supply up to its cap', async () => {
+		const series_name = 'reserved-' + t;
+		const src = '@params { mint: { speed: { default: 1 } } } @params';
+		await createSeries(bob, series_name, src, {
+			max_supply: '1',
+			mint: ['speed'],
+			reserved_supply: '2',
+		});
+
+		await bob.functionCall({
+			contractId,
+			methodName: 'series_mint_batch',
+			args: {
+				series_name,
+				recipients: [[aliceId, ['1'], []], [bobId, ['2'], []]],
+			},
+			gas: GAS,
+			attachedDeposit: parseNearAmount('0.5')
+		});
+		const series = await bob.viewFunction(contractId, 'series_data', { series_name });
+		expect(series.reserved_minted).toEqual('2');
+		expect(series.current_supply).toEqual('2');
+
+		try {
+			await bob.functionCall({
+				contractId,
+				methodName: 'series_mint_batch',
+				args: {
+					series_name,
+					recipients: [[aliceId, ['3'], []]],
+				},
+				gas: GAS,
+				attachedDeposit: parseNearAmount('0.5')
+			});
+			expect(false).toEqual(true);
+		} catch (e) {
+			expect(/Cannot mint anymore reserved/gi.test(e.toString())).toEqual(true);
+		}
+	});
+
+	test('alice pays for an approval from her storage deposit and withdraws the rest', async () => {
+		const token_id = 'reserved-' + t + SERIES_VARIANT_DELIMETER + 0;
+		await alice.functionCall({
+			contractId,
+			methodName: 'storage_deposit',
+			args: {},
+			gas: GAS,
+			attachedDeposit: parseNearAmount('0.1')
+		});
+		const before = await alice.viewFunction(contractId, 'storage_balance_of', { account_id: aliceId });
+		expect(before.total).toEqual(parseNearAmount('0.1'));
+
+		/// 1 yocto does not cover the approval, the shortfall comes from the storage deposit
+		await alice.functionCall({
+			contractId,
+			methodName: 'nft_approve',
+			args: {
+				token_id,
+				account_id: marketId,
+			},
+			gas: GAS,
+			attachedDeposit: '1'
+		});
+		const approved = await alice.viewFunction(contractId, 'nft_is_approved', {
+			token_id,
+			approved_account_id: marketId,
+		});
+		expect(approved).toEqual(true);
+		const after = await alice.viewFunction(contractId, 'storage_balance_of', { account_id: aliceId });
+		expect(new BN(after.total).lt(new BN(before.total))).toEqual(true);
+
+		await alice.functionCall({
+			contractId,
+			methodName: 'storage_withdraw',
+			args: {},
+			gas: GAS,
+			attachedDeposit: '1'
+		});
+		const withdrawn = await alice.viewFunction(contractId, 'storage_balance_of', { account_id: aliceId });
+		const { min } = await alice.viewFunction(contractId, 'storage_balance_bounds', {});
+		expect(withdrawn).toEqual({ total: min, available: '0' });
+	});
+
+	test('alice batch transfers tokens to bob and the approvals are cleared', async () => {
+		const token_ids = [
+			'reserved-' + t + SERIES_VARIANT_DELIMETER + 0,
+			'unique-args-' + t + SERIES_VARIANT_DELIMETER + 1,
+		];
+		await alice.functionCall({
+			contractId,
+			methodName: 'nft_batch_transfer',
+			args: {
+				receiver_id: bobId,
+				token_ids,
+			},
+			gas: GAS,
+			attachedDeposit: '1'
+		});
+
+		/// the market approval on the first token is refunded to alice with the transfer
+		for (const token_id of token_ids) {
+			const token = await bob.viewFunction(contractId, 'nft_token', { token_id });
+			expect(token.owner_id).toEqual(bobId);
+			expect(token.approved_account_ids).toEqual({});
+		}
+
+		try {
+			await alice.functionCall({
+				contractId,
+				methodName: 'nft_batch_transfer',
+				args: {
+					receiver_id: aliceId,
+					token_ids,
+				},
+				gas: GAS,
+				attachedDeposit: '1'
+			});
+			expect(false).toEqual(true);
+		} catch (e) {
+			expect(/Unauthorized/gi.test(e.toString())).toEqual(true);
+		}
+	});
+
+	test('minting is rejected while paused and resumes after unpausing', async () => {
+		const series_mint_args = { series_name: 'unique-args-' + t, mint: ['5'], owner: [] };
+		await contractAccount.functionCall({
+			contractId,
+			methodName: 'set_feature_paused',
+			args: { feature: 'minting', paused: true },
+			gas: GAS,
+			attachedDeposit: '1'
+		});
+		expect(await alice.viewFunction(contractId, 'paused_features', {})).toEqual(['minting']);
+
+		try {
+			await alice.functionCall({
+				contractId,
+				methodName: 'series_mint',
+				args: { series_mint_args },
+				gas: GAS,
+				attachedDeposit: parseNearAmount('0.2')
+			});
+			expect(false).toEqual(true);
+		} catch (e) {
+			expect(/Minting is paused/gi.test(e.toString())).toEqual(true);
+		}
+
+		await contractAccount.functionCall({
+			contractId,
+			methodName: 'set_feature_paused',
+			args: { feature: 'minting', paused: false },
+			gas: GAS,
+			attachedDeposit: '1'
+		});
+		await alice.functionCall({
+			contractId,
+			methodName: 'series_mint',
+			args: { series_mint_args },
+			gas: GAS,
+			attachedDeposit: parseNearAmount('0.2')
+		});
+		const token = await alice.viewFunction(contractId, 'nft_token', {
+			token_id: series_mint_args.series_name + SERIES_VARIANT_DELIMETER + 2
+		});
+		expect(token.owner_id).toEqual(aliceId);
+	});
+
+	test('owner grants the pauser role to bob', async () => {
+		try {
+			await bob.functionCall({
+				contractId,
+				methodName: 'set_feature_paused',
+				args: { feature: 'transfers', paused: true },
+				gas: GAS,
+				attachedDeposit: '1'
+			});
+			expect(false).toEqual(true);
+		} catch (e) {
+			expect(/Requires Pauser role/gi.test(e.toString())).toEqual(true);
+		}
+
+		/// role changes need a full access key
+		try {
+			await contractAccount.functionCall({
+				contractId,
+				methodName: 'grant_role',
+				args: { role: 'pauser', account_id: bobId },
+				gas: GAS,
+			});
+			expect(false).toEqual(true);
+		} catch (e) {
+			expect(/Requires attached deposit of exactly 1 yoctoNEAR/gi.test(e.toString())).toEqual(true);
+		}
+		await contractAccount.functionCall({
+			contractId,
+			methodName: 'grant_role',
+			args: { role: 'pauser', account_id: bobId },
+			gas: GAS,
+			attachedDeposit: '1'
+		});
+		expect(await bob.viewFunction(contractId, 'has_role', { role: 'pauser', account_id: bobId })).toEqual(true);
+
+		for (const paused of [true, false]) {
+			await bob.functionCall({
+				contractId,
+				methodName: 'set_feature_paused',
+				args: { feature: 'transfers', paused },
+				gas: GAS,
+				attachedDeposit: '1'
+			});
+		}
+		expect(await bob.viewFunction(contractId, 'paused_features', {})).toEqual([]);
+
+		await contractAccount.functionCall({
+			contractId,
+			methodName: 'revoke_role',
+			args: { role: 'pauser', account_id: bobId },
+			gas: GAS,
+			attachedDeposit: '1'
+		});
+		expect(await bob.viewFunction(contractId, 'has_role', { role: 'pauser', account_id: bobId })).toEqual(false);
+	});
+
+	test('ownership moves only when the pending owner accepts', async () => {
+		await contractAccount.functionCall({
+			contractId,
+			methodName: 'transfer_ownership',
+			args: { new_owner_id: aliceId },
+			gas: GAS,
+			attachedDeposit: '1'
+		});
+		expect(await alice.viewFunction(contractId, 'get_pending_owner_id', {})).toEqual(aliceId);
+		expect(await alice.viewFunction(contractId, 'get_owner_id', {})).toEqual(contractId);
+
+		try {
+			await bob.functionCall({
+				contractId,
+				methodName: 'accept_ownership',
+				args: {},
+				gas: GAS,
+				attachedDeposit: '1'
+			});
+			expect(false).toEqual(true);
+		} catch (e) {
+			expect(/Must be pending owner/gi.test(e.toString())).toEqual(true);
+		}
+
+		await alice.functionCall({
+			contractId,
+			methodName: 'accept_ownership',
+			args: {},
+			gas: GAS,
+			attachedDeposit: '1'
+		});
+		expect(await alice.viewFunction(contractId, 'get_owner_id', {})).toEqual(aliceId);
+		expect(await alice.viewFunction(contractId, 'get_pending_owner_id', {})).toEqual(null);
+
+		/// hand the contract back so later tests keep using contractAccount as owner
+		await alice.functionCall({
+			contractId,
+			methodName: 'transfer_ownership',
+			args: { new_owner_id: contractId },
+			gas: GAS,
+			attachedDeposit: '1'
+		});
+		await contractAccount.functionCall({
+			contractId,
+			methodName: 'accept_ownership',
+			args: {},
+			gas: GAS,
+			attachedDeposit: '1'
+		});
+		expect(await alice.viewFunction(contractId, 'get_owner_id', {})).toEqual(contractId);
+	});
+
+	test('series_mint splits the mint price between royalty receivers and the series owner', async () => {
+		const series_name = 'payout-split-' + t;
+		const src = '@params { mint: { speed: { default: 1 } } } @params';
+		const mint_price = parseNearAmount('1');
+		await createSeries(bob, series_name, src, { mint: ['speed'], mint_price }, { [aliceId]: 1000 });
+
+		const contract_royalty = await bob.viewFunction(contractId, 'get_contract_royalty', {});
+		const aliceBefore = new BN((await getAccountBalance(aliceId)).total);
+		const bobBefore = new BN((await getAccountBalance(bobId)).total);
+		await contractAccount.functionCall({
+			contractId,
+			methodName: 'series_mint',
+			args: {
+				series_mint_args: { series_name, mint: ['1'], owner: [] }
+			},
+			gas: GAS,
+			attachedDeposit: parseNearAmount('1.1')
+		});
+		const aliceAfter = new BN((await getAccountBalance(aliceId)).total);
+		const bobAfter = new BN((await getAccountBalance(bobId)).total);
+
+		/// royalty is in basis points, bob gets what the royalty receivers and the contract do not
+		const aliceShare = new BN(mint_price).muln(1000).divn(10000);
+		const contractShare = new BN(mint_price).muln(contract_royalty).divn(10000);
+		expect(aliceAfter.sub(aliceBefore).toString()).toEqual(aliceShare.toString());
+		expect(bobAfter.sub(bobBefore).toString()).toEqual(
+			new BN(mint_price).sub(aliceShare).sub(contractShare).toString()
+		);
+	});
+
+	test('bob uploads series src in chunks and finalize checks the hash', async () => {
+		const src = '@params { mint: { speed: { default: 1 } } } @params';
+		const chunks = [src.slice(0, 20), src.slice(20)];
+		/// the bad series is created with the hash of a different src of the same length
+		for (const [series_name, created_src] of [
+			['chunked-' + t, src],
+			['chunked-bad-' + t, src.toUpperCase()],
+		]) {
+			await createSeries(bob, series_name, created_src, { mint: ['speed'] });
+			await bob.functionCall({
+				contractId,
+				methodName: 'series_src_append',
+				args: { series_name, chunk: chunks[0] },
+				gas: GAS,
+			});
+		}
+
+		/// finalize waits for every declared byte
+		try {
+			await bob.functionCall({
+				contractId,
+				methodName: 'series_src_finalize',
+				args: { series_name: 'chunked-' + t },
+				gas: GAS,
+			});
+			expect(false).toEqual(true);
+		} catch (e) {
+			expect(/Must be exactly the same bytes/gi.test(e.toString())).toEqual(true);
+		}
+
+		for (const series_name of ['chunked-' + t, 'chunked-bad-' + t]) {
+			await bob.functionCall({
+				contractId,
+				methodName: 'series_src_append',
+				args: { series_name, chunk: chunks[1] },
+				gas: GAS,
+			});
+		}
+		await bob.functionCall({
+			contractId,
+			methodName: 'series_src_finalize',
+			args: { series_name: 'chunked-' + t },
+			gas: GAS,
+		});
+		const series = await bob.viewFunction(contractId, 'series_data', { series_name: 'chunked-' + t });
+		expect(series.src).toEqual(src);
+
+		try {
+			await bob.functionCall({
+				contractId,
+				methodName: 'series_src_finalize',
+				args: { series_name: 'chunked-bad-' + t },
+				gas: GAS,
+			});
+			expect(false).toEqual(true);
+		} catch (e) {
+			expect(/Src does not match src_hash/gi.test(e.toString())).toEqual(true);
+		}
+	});
+
+	test('bob reduces supply, closes and deletes his series', async () => {
+		const series_name = 'reserved-' + t;
+		try {
+			await bob.functionCall({
+				contractId,
+				methodName: 'series_reduce_max_supply',
+				args: { series_name, max_supply: '2' },
+				gas: GAS,
+				attachedDeposit: '1'
+			});
+			expect(false).toEqual(true);
+		} catch (e) {
+			expect(/Can only reduce max_supply/gi.test(e.toString())).toEqual(true);
+		}
+		await bob.functionCall({
+			contractId,
+			methodName: 'series_reduce_max_supply',
+			args: { series_name, max_supply: '0' },
+			gas: GAS,
+			attachedDeposit: '1'
+		});
+		await bob.functionCall({
+			contractId,
+			methodName: 'series_close',
+			args: { series_name },
+			gas: GAS,
+			attachedDeposit: '1'
+		});
+		const series = await bob.viewFunction(contractId, 'series_data', { series_name });
+		expect(series.params.max_supply).toEqual('0');
+		expect(series.closed).toEqual(true);
+
+		try {
+			await bob.functionCall({
+				contractId,
+				methodName: 'series_mint_batch',
+				args: {
+					series_name,
+					recipients: [[bobId, ['3'], []]],
+				},
+				gas: GAS,
+				attachedDeposit: parseNearAmount('0.5')
+			});
+			expect(false).toEqual(true);
+		} catch (e) {
+			expect(/is closed/gi.test(e.toString())).toEqual(true);
+		}
+
+		/// only series without minted tokens can be deleted
+		try {
+			await bob.functionCall({
+				contractId,
+				methodName: 'series_delete',
+				args: { series_name },
+				gas: GAS,
+				attachedDeposit: '1'
+			});
+			expect(false).toEqual(true);
+		} catch (e) {
+			expect(/Cannot delete a series with minted tokens/gi.test(e.toString())).toEqual(true);
+		}
+		await bob.functionCall({
+			contractId,
+			methodName: 'series_delete',
+			args: { series_name: 'chunked-bad-' + t },
+			gas: GAS,
+			attachedDeposit: '1'
+		});
+		try {
+			await bob.viewFunction(contractId, 'series_data', { series_name: 'chunked-bad-' + t });
+			expect(false).toEqual(true);
+		} catch (e) {
+			expect(/No series/gi.test(e.toString())).toEqual(true);
+		}
+	});
+
+	test('series_transfer moves the series between owner indexes', async () => {
+		const series_name = 'chunked-' + t;
+		const bobSupply = await bob.viewFunction(contractId, 'series_supply_for_owner', { account_id: bobId });
+		const aliceSupply = await bob.viewFunction(contractId, 'series_supply_for_owner', { account_id: aliceId });
+		await bob.functionCall({
+			contractId,
+			methodName: 'series_transfer',
+			args: {
+				series_name,
+				new_owner_id: aliceId,
+			},
+			gas: GAS,
+			attachedDeposit: parseNearAmount('0.1')
+		});
+
+		const series = await bob.viewFunction(contractId, 'series_data', { series_name });
+		expect(series.owner_id).toEqual(aliceId);
+		expect(await bob.viewFunction(contractId, 'series_supply_for_owner', { account_id: bobId }))
+			.toEqual(new BN(bobSupply).subn(1).toString());
+		expect(await bob.viewFunction(contractId, 'series_supply_for_owner', { account_id: aliceId }))
+			.toEqual(new BN(aliceSupply).addn(1).toString());
+		const aliceSeries = await bob.viewFunction(contractId, 'series_per_owner', {
+			account_id: aliceId,
+			from_index: '0',
+			limit: 100,
+		});
+		expect(aliceSeries.map(({ series_name }) => series_name)).toContain(series_name);
+
+		try {
+			await bob.functionCall({
+				contractId,
+				methodName: 'series_src_append',
+				args: { series_name, chunk: ' ' },
+				gas: GAS,
+			});
+			expect(false).toEqual(true);
+		} catch (e) {
+			expect(/Must be series owner/gi.test(e.toString())).toEqual(true);
+		}
+	});
+
+	test('series mint args are checked against the param schema', async () => {
+		const series_name = 'typed-' + t;
+		const src = '@params { mint: { speed: { default: 1 }, color: { default: 1 } } } @params';
+		const mint_types = [{ type: 'int', min: 0, max: 10 }, { type: 'color' }];
+		await createSeries(bob, series_name, src, {
+			mint: ['speed', 'color'],
+			mint_price: parseNearAmount('0.1'),
+			mint_types,
+		});
+		/// types no value can satisfy are rejected up front
+		for (const bad_type of [{ type: 'enum', values: [] }, { type: 'int', min: 5, max: 1 }, { type: 'string', max_len: 0 }]) {
+			try {
+				await createSeries(bob, 'typed-bad-' + t, src, {
+					mint: ['speed', 'color'],
+					mint_types: [bad_type, { type: 'color' }],
+				});
+				expect(false).toEqual(true);
+			} catch (e) {
+				expect(/Invalid type for param speed/gi.test(e.toString())).toEqual(true);
+			}
+		}
+
+		const schema = await alice.viewFunction(contractId, 'series_params_schema', { series_name });
+		expect(schema).toEqual({
+			mint: [{ name: 'speed', ...mint_types[0] }, { name: 'color', ...mint_types[1] }],
+			owner: [],
+		});
+
+		try {
+			await alice.functionCall({
+				contractId,
+				methodName: 'series_mint',
+				args: {
+					series_mint_args: { series_name, mint: ['11', '"#ff0000"'], owner: [] }
+				},
+				gas: GAS,
+				attachedDeposit: parseNearAmount('0.2')
+			});
+			expect(false).toEqual(true);
+		} catch (e) {
+			expect(/Invalid value for param speed/gi.test(e.toString())).toEqual(true);
+		}
+
+		await alice.functionCall({
+			contractId,
+			methodName: 'series_mint',
+			args: {
+				series_mint_args: { series_name, mint: ['3', '"#ff0000"'], owner: [] }
+			},
+			gas: GAS,
+			attachedDeposit: parseNearAmount('0.2')
+		});
+		const token = await alice.viewFunction(contractId, 'nft_token', {
+			token_id: series_name + SERIES_VARIANT_DELIMETER + 0
+		});
+		expect(token.series_args.mint).toEqual(['3', '"#ff0000"']);
+	});
+
+	test('owner args history is paged oldest first', async () => {
+		const token_id = 'unique-args-' + t + SERIES_VARIANT_DELIMETER + 0;
+		for (const color of ['3', '4']) {
+			await alice.functionCall({
+				contractId,
+				methodName: 'update_token_owner_args',
+				args: {
+					token_id,
+					owner_args: [color]
+				},
+				gas: GAS,
+				attachedDeposit: parseNearAmount('0.1')
+			});
+		}
+
+		/// each entry holds the owner args a token had before that update
+		const history = await alice.viewFunction(contractId, 'token_owner_args_history', {
+			token_id,
+			from_index: '0',
+			limit: 10,
+		});
+		expect(history.map(({ owner_args }) => owner_args)).toEqual([[], ['2'], ['3']]);
+		expect(history.every(({ account_id }) => account_id === aliceId)).toEqual(true);
+
+		const page = await alice.viewFunction(contractId, 'token_owner_args_history', {
+			token_id,
+			from_index: '1',
+			limit: 1,
+		});
+		expect(page.map(({ owner_args }) => owner_args)).toEqual([['2']]);
+		const token = await alice.viewFunction(contractId, 'nft_token', { token_id });
+		expect(token.series_args.owner).toEqual(['4']);
+		expect(token.num_owner_args_updates).toEqual(3);
+	});
+
+	test('revoking the market approval delists the sale', async () => {
+		const token_id = 'unique-args-' + t + SERIES_VARIANT_DELIMETER + 2;
+		const nft_contract_token = contractId + '||' + token_id;
+		const listToken = () => alice.functionCall({
+			contractId,
+			methodName: 'nft_approve',
+			args: {
+				token_id,
+				account_id: marketId,
+				msg: JSON.stringify({
+					sale_conditions: [
+						{ ft_token_id: "near", price: parseNearAmount('1')}
+					]
+				})
+			},
+			gas: GAS,
+			attachedDeposit: new BN(storagePerSale).add(new BN(parseNearAmount('0.1'))).toString()
+		});
+
+		await listToken();
+		expect((await alice.viewFunction(marketId, 'get_sale', { nft_contract_token })).owner_id).toEqual(aliceId);
+		await alice.functionCall({
+			contractId,
+			methodName: 'nft_revoke',
+			args: { token_id, account_id: marketId },
+			gas: GAS,
+			attachedDeposit: '1'
+		});
+		expect(await alice.viewFunction(marketId, 'get_sale', { nft_contract_token })).toEqual(null);
+
+		/// revoking fails rather than leaving the market unnotified when gas runs short
+		await listToken();
+		try {
+			await alice.functionCall({
+				contractId,
+				methodName: 'nft_revoke_all',
+				args: { token_id },
+				gas: '25000000000000',
+				attachedDeposit: '1'
+			});
+			expect(false).toEqual(true);
+		} catch (e) {
+			expect(/gas to notify 1 revoked accounts/gi.test(e.toString())).toEqual(true);
+		}
+		expect((await alice.viewFunction(marketId, 'get_sale', { nft_contract_token })).owner_id).toEqual(aliceId);
+
+		await alice.functionCall({
+			contractId,
+			methodName: 'nft_revoke_all',
+			args: { token_id },
+			gas: GAS,
+			attachedDeposit: '1'
+		});
+		expect(await alice.viewFunction(marketId, 'get_sale', { nft_contract_token })).toEqual(null);
+	});
+
+	test('deleting a series releases its mint phase allowlists', async () => {
+		const series_name = 'allowlisted-' + t;
+		const src = '@params { mint: { speed: { default: 1 } } } @params';
+		const bytesBefore = await getAccountBytes(contractId);
+		await createSeries(bob, series_name, src, {
+			mint: ['speed'],
+			phases: [{ allowlist: true }],
+		});
+		await bob.functionCall({
+			contractId,
+			methodName: 'series_allowlist_add',
+			args: { series_name, phase_index: 0, account_ids: [aliceId, bobId] },
+			gas: GAS,
+			attachedDeposit: parseNearAmount('0.1')
+		});
+		expect(await bob.viewFunction(contractId, 'series_allowlist_contains', {
+			series_name, phase_index: 0, account_id: aliceId
+		})).toEqual(true);
+
+		await bob.functionCall({
+			contractId,
+			methodName: 'series_delete',
+			args: { series_name },
+			gas: GAS,
+			attachedDeposit: '1'
+		});
+		expect(await getAccountBytes(contractId)).toEqual(bytesBefore);
+	});
+
+	test('burned token ids are never reused by later mints', async () => {
+		const series_name = 'burn-ids-' + t;
+		const src = '@params { mint: { speed: { default: 1 } } } @params';
+		await createSeries(bob, series_name, src, { mint: ['speed'], mint_price: parseNearAmount('0.1') });
+		const mint = () => alice.functionCall({
+			contractId,
+			methodName: 'series_mint',
+			args: {
+				series_mint_args: { series_name, mint: ['1'], owner: [] }
+			},
+			gas: GAS,
+			attachedDeposit: parseNearAmount('0.2')
+		});
+		await mint();
+		await mint();
+
+		await alice.functionCall({
+			contractId,
+			methodName: 'nft_burn',
+			args: { token_id: series_name + SERIES_VARIANT_DELIMETER + 1 },
+			gas: GAS,
+			attachedDeposit: '1'
+		});
+		let series = await alice.viewFunction(contractId, 'series_data', { series_name });
+		expect(series.minted_count).toEqual('2');
+		expect(series.current_supply).toEqual('1');
+
+		/// the next mint continues from minted_count instead of refilling the burned id
+		await mint();
+		series = await alice.viewFunction(contractId, 'series_data', { series_name });
+		expect(series.minted_count).toEqual('3');
+		expect(series.current_supply).toEqual('2');
+		expect(await alice.viewFunction(contractId, 'nft_token', {
+			token_id: series_name + SERIES_VARIANT_DELIMETER + 1
+		})).toEqual(null);
+		const token = await alice.viewFunction(contractId, 'nft_token', {
+			token_id: series_name + SERIES_VARIANT_DELIMETER + 2
+		});
+		expect(token.owner_id).toEqual(aliceId);
+	});
+
+	test('owner args update fee is split over the series royalty and the rest goes to the series owner', async () => {
+		const series_name = 'owner-fee-' + t;
+		const src = '@params { owner: { color: { default: 1 } } } @params';
+		const fee = parseNearAmount('1');
+		await createSeries(bob, series_name, src, {
+			owner: ['color'],
+			mint_price: parseNearAmount('0.1'),
+			owner_args_update_fee: fee,
+			/// an hour in nanoseconds
+			owner_args_update_cooldown: '3600000000000',
+		}, { [aliceId]: 2000 });
+		await contractAccount.functionCall({
+			contractId,
+			methodName: 'series_mint',
+			args: {
+				series_mint_args: { series_name, mint: [], owner: [] }
+			},
+			gas: GAS,
+			attachedDeposit: parseNearAmount('0.2')
+		});
+		const token_id = series_name + SERIES_VARIANT_DELIMETER + 0;
+		const updateOwnerArgs = (owner_args) => contractAccount.functionCall({
+			contractId,
+			methodName: 'update_token_owner_args',
+			args: { token_id, owner_args },
+			gas: GAS,
+			attachedDeposit: parseNearAmount('1.1')
+		});
+
+		const aliceBefore = new BN((await getAccountBalance(aliceId)).total);
+		const bobBefore = new BN((await getAccountBalance(bobId)).total);
+		await updateOwnerArgs(['2']);
+		const aliceAfter = new BN((await getAccountBalance(aliceId)).total);
+		const bobAfter = new BN((await getAccountBalance(bobId)).total);
+		const aliceShare = new BN(fee).muln(2000).divn(10000);
+		expect(aliceAfter.sub(aliceBefore).toString()).toEqual(aliceShare.toString());
+		expect(bobAfter.sub(bobBefore).toString()).toEqual(new BN(fee).sub(aliceShare).toString());
+
+		try {
+			await updateOwnerArgs(['3']);
+			expect(false).toEqual(true);
+		} catch (e) {
+			expect(/Owner args cannot be updated again until/gi.test(e.toString())).toEqual(true);
+		}
+	});
+
+	test('owner args updates stop at max_owner_args_updates', async () => {
+		const series_name = 'owner-max-' + t;
+		const src = '@params { owner: { color: { default: 1 } } } @params';
+		await createSeries(bob, series_name, src, {
+			owner: ['color'],
+			mint_price: parseNearAmount('0.1'),
+			max_owner_args_updates: 1,
+		});
+		await alice.functionCall({
+			contractId,
+			methodName: 'series_mint',
+			args: {
+				series_mint_args: { series_name, mint: [], owner: [] }
+			},
+			gas: GAS,
+			attachedDeposit: parseNearAmount('0.2')
+		});
+		const token_id = series_name + SERIES_VARIANT_DELIMETER + 0;
+		const updateOwnerArgs = (owner_args) => alice.functionCall({
+			contractId,
+			methodName: 'update_token_owner_args',
+			args: { token_id, owner_args },
+			gas: GAS,
+			attachedDeposit: parseNearAmount('0.1')
+		});
+
+		await updateOwnerArgs(['2']);
+		try {
+			await updateOwnerArgs(['3']);
+			expect(false).toEqual(true);
+		} catch (e) {
+			expect(/Token has reached the max of 1 owner args updates/gi.test(e.toString())).toEqual(true);
+		}
+	});
+
+	test('mint phases enforce allowlists and caps, started phases stay in place', async () => {
+		const series_name = 'phased-' + t;
+		const src = '@params { mint: { speed: { default: 1 } } } @params';
+		await createSeries(bob, series_name, src, {
+			mint: ['speed'],
+			mint_price: parseNearAmount('0.1'),
+			phases: [{ max_supply: '1', allowlist: true }],
+		});
+		await bob.functionCall({
+			contractId,
+			methodName: 'series_allowlist_add',
+			args: { series_name, phase_index: 0, account_ids: [aliceId] },
+			gas: GAS,
+			attachedDeposit: parseNearAmount('0.1')
+		});
+		const mint = (account, receiver_id) => account.functionCall({
+			contractId,
+			methodName: 'series_mint',
+			args: {
+				series_mint_args: { series_name, mint: ['1'], owner: [], receiver_id }
+			},
+			gas: GAS,
+			attachedDeposit: parseNearAmount('0.2')
+		});
+
+		try {
+			await mint(alice, bobId);
+			expect(false).toEqual(true);
+		} catch (e) {
+			expect(/Receiver not on mint phase allowlist/gi.test(e.toString())).toEqual(true);
+		}
+
+		await mint(bob, aliceId);
+		let phase = await alice.viewFunction(contractId, 'series_mint_phase', { series_name });
+		expect(phase.index).toEqual(0);
+		expect(phase.minted).toEqual('1');
+		expect(phase.remaining).toEqual('0');
+		try {
+			await mint(alice);
+			expect(false).toEqual(true);
+		} catch (e) {
+			expect(/Cannot mint anymore in this phase/gi.test(e.toString())).toEqual(true);
+		}
+
+		/// an hour from now in nanoseconds
+		const starts_at = new BN(Date.now()).add(new BN(3600000)).mul(new BN(1000000)).toString();
+		try {
+			await bob.functionCall({
+				contractId,
+				methodName: 'series_set_mint_phases',
+				args: { series_name, phases: [{ starts_at, max_supply: '2', allowlist: true }] },
+				gas: GAS,
+				attachedDeposit: parseNearAmount('0.1')
+			});
+			expect(false).toEqual(true);
+		} catch (e) {
+			expect(/Cannot remove or move mint phase 0 after it started/gi.test(e.toString())).toEqual(true);
+		}
+
+		/// raising the cap of the started phase keeps what was minted in it
+		await bob.functionCall({
+			contractId,
+			methodName: 'series_set_mint_phases',
+			args: { series_name, phases: [{ max_supply: '2', allowlist: true }] },
+			gas: GAS,
+			attachedDeposit: parseNearAmount('0.1')
+		});
+		phase = await alice.viewFunction(contractId, 'series_mint_phase', { series_name });
+		expect(phase.minted).toEqual('1');
+		expect(phase.remaining).toEqual('1');
+		await mint(alice);
+	});
+
+	test('token metadata is rendered from the series templates', async () => {
+		const series_name = 'templated-' + t;
+		const src = '@params { mint: { speed: { default: 1 } } } @params';
+		await createSeries(bob, series_name, src, {
+			mint: ['speed'],
+			mint_price: parseNearAmount('0.1'),
+			reserved_supply: '2',
+			media_template: 'https://example.com/{series_name}/{edition}/{args}.png',
+			reference_template: 'https://example.com/{token_id}.json',
+		});
+		await alice.functionCall({
+			contractId,
+			methodName: 'series_mint',
+			args: {
+				series_mint_args: { series_name, mint: ['7'], owner: [] }
+			},
+			gas: GAS,
+			attachedDeposit: parseNearAmount('0.2')
+		});
+
+		/// token ids are 0 based, editions in the title and templates 1 based
+		const token_id = series_name + SERIES_VARIANT_DELIMETER + 0;
+		const { metadata } = await alice.viewFunction(contractId, 'nft_token', { token_id });
+		expect(metadata.title).toEqual(series_name + ' #1');
+		expect(metadata.media).toEqual(`https://example.com/${series_name}/1/7.png`);
+		expect(metadata.reference).toEqual(`https://example.com/${token_id}.json`);
+		expect(metadata.copies).toEqual('12');
+	});
+
+	test('series names are validated at create and looked up in any case', async () => {
+		const src = '@params { mint: { speed: { default: 1 } } } @params';
+		for (const bad_name of ['bad:name-' + t, 'bad name-' + t]) {
+			try {
+				await createSeries(bob, bad_name, src, { mint: ['speed'] });
+				expect(false).toEqual(true);
+			} catch (e) {
+				expect(/Series name may only contain/gi.test(e.toString())).toEqual(true);
+			}
+		}
+
+		/// names are stored lowercased, lookups lowercase whatever case they are given
+		const series_name = 'mixed-case-' + t;
+		await createSeries(bob, 'Mixed-Case-' + t, src, { mint: ['speed'], mint_price: parseNearAmount('0.1') });
+		const series = await alice.viewFunction(contractId, 'series_data', { series_name: 'MIXED-case-' + t });
+		expect(series.series_name).toEqual(series_name);
+		await alice.functionCall({
+			contractId,
+			methodName: 'series_mint',
+			args: {
+				series_mint_args: { series_name: 'mIxEd-CaSe-' + t, mint: ['1'], owner: [] }
+			},
+			gas: GAS,
+			attachedDeposit: parseNearAmount('0.2')
+		});
+		const token = await alice.viewFunction(contractId, 'nft_token', {
+			token_id: series_name + SERIES_VARIANT_DELIMETER + 0
+		});
+		expect(token.series_args.series_name).toEqual(series_name);
+	});
+
+	test('missing mint args are derived from the token seed and pass the schema', async () => {
+		const series_name = 'seeded-' + t;
+		const src = '@params { mint: { speed: { default: 1 }, color: { default: 1 } } } @params';
+		await createSeries(bob, series_name, src, {
+			mint: ['speed', 'color'],
+			mint_types: [{ type: 'int', min: 0, max: 10 }, { type: 'color' }],
+			derive_mint_args_from_seed: true,
+			mint_price: parseNearAmount('0.1'),
+		});
+		for (const mint of [[], ['5']]) {
+			await alice.functionCall({
+				contractId,
+				methodName: 'series_mint',
+				args: {
+					series_mint_args: { series_name, mint, owner: [] }
+				},
+				gas: GAS,
+				attachedDeposit: parseNearAmount('0.2')
+			});
+		}
+
+		for (const i of [0, 1]) {
+			const token = await alice.viewFunction(contractId, 'nft_token', {
+				token_id: series_name + SERIES_VARIANT_DELIMETER + i
+			});
+			expect(/^[0-9a-f]{64}$/.test(token.seed)).toEqual(true);
+			const [speed, color] = token.series_args.mint;
+			expect(parseInt(speed) >= 0 && parseInt(speed) <= 10).toEqual(true);
+			expect(/^"0x[0-9a-f]{6}"$/.test(color)).toEqual(true);
+		}
+		/// supplied args are kept, only the missing ones are derived
+		const token = await alice.viewFunction(contractId, 'nft_token', {
+			token_id: series_name + SERIES_VARIANT_DELIMETER + 1
+		});
+		expect(token.series_args.mint[0]).toEqual('5');
+	});
+
 });
\ No newline at end of file